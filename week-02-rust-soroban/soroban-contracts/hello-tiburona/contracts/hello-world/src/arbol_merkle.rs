@@ -0,0 +1,86 @@
+#![cfg(test)]
+
+// ============================================================================
+// ÁRBOL MERKLE DEL LADO DEL HOST (SOLO TESTS)
+// ============================================================================
+// Construye el árbol de asistentes con sha2 (fuera del contrato) usando las
+// mismas reglas que hello_con_prueba():
+//   - Hoja = sha256(XDR de la dirección)
+//   - Nodo = sha256(menor || mayor)
+//   - Un nodo sin pareja sube tal cual al siguiente nivel
+// ============================================================================
+
+extern crate std;
+
+use sha2::{Digest, Sha256};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env, Vec};
+use std::vec::Vec as StdVec;
+
+type Hash = [u8; 32];
+
+pub struct ArbolMerkle {
+    // niveles[0] = hojas, niveles.last() = [raíz]
+    niveles: StdVec<StdVec<Hash>>,
+}
+
+fn sha256(datos: &[u8]) -> Hash {
+    Sha256::digest(datos).into()
+}
+
+fn hash_par(a: &Hash, b: &Hash) -> Hash {
+    let (menor, mayor) = if a <= b { (a, b) } else { (b, a) };
+    let mut datos = [0u8; 64];
+    datos[..32].copy_from_slice(menor);
+    datos[32..].copy_from_slice(mayor);
+    sha256(&datos)
+}
+
+impl ArbolMerkle {
+    pub fn nuevo(env: &Env, usuarios: &[Address]) -> Self {
+        assert!(!usuarios.is_empty(), "el árbol necesita al menos una hoja");
+
+        let hojas: StdVec<Hash> = usuarios
+            .iter()
+            .map(|u| {
+                let xdr: StdVec<u8> = u.clone().to_xdr(env).iter().collect();
+                sha256(&xdr)
+            })
+            .collect();
+
+        let mut niveles = std::vec![hojas];
+        while niveles.last().unwrap().len() > 1 {
+            let siguiente = niveles
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|par| match par {
+                    [a, b] => hash_par(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            niveles.push(siguiente);
+        }
+
+        ArbolMerkle { niveles }
+    }
+
+    pub fn raiz(&self, env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &self.niveles.last().unwrap()[0])
+    }
+
+    // Hermanos de la hoja `indice` desde abajo hacia la raíz
+    pub fn prueba(&self, env: &Env, mut indice: usize) -> Vec<BytesN<32>> {
+        let mut prueba = Vec::new(env);
+
+        for nivel in &self.niveles[..self.niveles.len() - 1] {
+            let hermano = indice ^ 1;
+            if hermano < nivel.len() {
+                prueba.push_back(BytesN::from_array(env, &nivel[hermano]));
+            }
+            indice /= 2;
+        }
+
+        prueba
+    }
+}
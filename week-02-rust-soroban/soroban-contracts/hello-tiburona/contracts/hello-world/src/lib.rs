@@ -1,452 +1,8494 @@
 // HECHO POR LA TIBURONA SOFIA NAVARRO
 // ============================================================================
-// IMPLEMENTACIÓN DEL CONTRATO HELLO TIBURONA
+// CONFIGURACIÓN DEL CRATE
 // ============================================================================
-// Este bloque implementa todas las funciones públicas del contrato.
-// El macro #[contractimpl] convierte estas funciones en endpoints del contrato
-// que pueden ser llamados desde fuera de la blockchain.
+// #![no_std] = no usamos la librería estándar de Rust
+// Los contratos de Soroban compilan a WASM y el SDK provee sus propios tipos
+// (String, Vec, Map, etc.) que viven dentro del ambiente del contrato.
+// ============================================================================
+
+#![no_std]
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, contracterror,
+    contractmeta, contracttype, symbol_short, vec, xdr::{FromXdr, ToXdr}, Bytes, BytesN, Env,
+    IntoVal, InvokeError, Map, Symbol, Address, String, TryFromVal, Val, Vec
+};
+
+#[cfg(test)]
+mod arbol_merkle;
+
+// ============================================================================
+// METADATA DEL CONTRATO
+// ============================================================================
+// contractmeta! escribe pares clave/valor en el WASM (sección contractmetav0)
+// Se pueden leer sin llamar al contrato, ej: `stellar contract info meta`
+// La versión tiene que coincidir con VERSION_CONTRATO
+// ============================================================================
+contractmeta!(key = "name", val = "hello-tiburona");
+contractmeta!(key = "version", val = "2.0.0");
+contractmeta!(key = "source_repo", val = "https://github.com/sofiana-22/Codigo-Futura");
+
+// ============================================================================
+// ERRORES DEL CONTRATO
+// ============================================================================
+// #[contracterror] convierte el enum en errores que el cliente puede leer
+// Cada variante tiene un código numérico fijo: Error(Contract, #N)
+// Los códigos NUNCA se renumeran ni se reusan (test_codigos_de_error_estables)
+// Los comentarios /// de cada variante viajan en el spec del contrato:
+// es la tabla código → significado que ven los bindings generados
+// ============================================================================
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    /// El nombre recibido está vacío
+    NombreVacio = 1,
+    /// El nombre supera el límite de caracteres
+    NombreMuyLargo = 2,
+    /// Quien llama no es el admin
+    NoAutorizado = 3,
+    /// El contrato no tiene admin configurado (todavía no se llamó a initialize)
+    NoInicializado = 4,
+    /// Límite de nombre fuera de 1..=256
+    LimiteInvalido = 5,
+    /// Ya hay una sala con ese id
+    SalaYaExiste = 6,
+    /// La sala pedida nunca fue creada
+    SalaNoExiste = 7,
+    /// Un hook obligatorio falló al ejecutarse
+    HookFallido = 8,
+    /// Ya hay MAX_HOOKS hooks registrados
+    DemasiadosHooks = 9,
+    /// El hook a quitar no está registrado
+    HookNoExiste = 10,
+    /// El usuario está en la lista de bloqueados
+    UsuarioBloqueado = 11,
+    /// Modo lista activo y el usuario no está permitido
+    NoEstaEnLista = 12,
+    /// El lote supera MAX_LOTE elementos
+    LoteMuyGrande = 13,
+    /// No hay raíz Merkle para verificar pruebas
+    RaizNoConfigurada = 14,
+    /// La prueba Merkle no lleva a la raíz
+    PruebaInvalida = 15,
+    /// Esta dirección ya saludó con la raíz actual
+    HojaYaUsada = 16,
+    /// El ledger actual superó la expiración firmada
+    FirmaExpirada = 17,
+    /// El nonce no es el siguiente esperado para la clave
+    NonceInvalido = 18,
+    /// La passkey ya está vinculada a un usuario
+    PasskeyYaRegistrada = 19,
+    /// La passkey no está vinculada a ningún usuario
+    PasskeyNoRegistrada = 20,
+    /// Firmantes vacíos/repetidos o umbral fuera de 1..=N
+    ConsejoInvalido = 21,
+    /// Hay consejo: la acción debe pasar por propuesta
+    RequiereConsejo = 22,
+    /// No hay propuesta con ese id
+    PropuestaNoExiste = 23,
+    /// La propuesta venció sin llegar al umbral
+    PropuestaExpirada = 24,
+    /// La propuesta ya se ejecutó o fue cancelada
+    PropuestaCerrada = 25,
+    /// El firmante ya aprobó esta propuesta
+    YaAprobada = 26,
+    /// Hay MAX_PROPUESTAS pendientes
+    DemasiadasPropuestas = 27,
+    /// Hay retraso configurado: usar programar_*
+    RequiereProgramacion = 28,
+    /// No hay cambio programado con ese id
+    CambioNoExiste = 29,
+    /// Todavía no pasó el retraso del cambio
+    CambioNoListo = 30,
+    /// Hay MAX_CAMBIOS cambios en cola
+    DemasiadosCambios = 31,
+    /// Retraso mayor que MAX_RETRASO
+    RetrasoInvalido = 32,
+    /// No hay votación con ese id
+    VotacionNoExiste = 33,
+    /// El período de voto terminó o ya se finalizó
+    VotacionCerrada = 34,
+    /// El usuario ya votó en esta votación
+    YaVoto = 35,
+    /// El usuario no tenía saludos al crearse la votación
+    SinPesoDeVoto = 36,
+    /// Todavía no terminó el período de voto
+    VotacionAbierta = 37,
+    /// Quórum 0 o período fuera de rango
+    GobernanzaInvalida = 38,
+    /// El usuario está suspendido hasta cierto ledger
+    UsuarioSuspendido = 39,
+    /// No hay acción de moderación con ese id
+    AccionNoExiste = 40,
+    /// La acción ya fue apelada una vez
+    YaApelada = 41,
+    /// No hay apelación pendiente para resolver
+    ApelacionNoPendiente = 42,
+    /// El usuario no tiene saludo para moderar
+    SinSaludo = 43,
+    /// Mensaje de apelación vacío o muy largo
+    ApelacionInvalida = 44,
+    /// El nombre contiene una palabra prohibida
+    NombreProhibido = 45,
+    /// Palabra prohibida vacía, muy larga o repetida
+    PalabraInvalida = 46,
+    /// Se alcanzó MAX_PALABRAS_PROHIBIDAS
+    DemasiadasPalabras = 47,
+    /// El nombre no es el que el usuario tiene registrado
+    NombreNoCoincide = 48,
+    /// initialize se llamó sobre un contrato que ya tiene admin.
+    /// Hasta la v1.0.0 este caso devolvía #4 (NoInicializado): ver Error::codigo_v1
+    YaInicializado = 49,
+    /// Un contador llegó a u64::MAX y el modo de desbordamiento es Fallar
+    ContadorDesbordado = 50,
+}
+
+impl Error {
+    // ========================================================================
+    // COMPATIBILIDAD CON CLIENTES v1.0.0
+    // ========================================================================
+    // Hasta la v1.0.0, initialize() sobre un contrato ya inicializado
+    // devolvía #4 (NoInicializado). Los clientes que comparaban contra #4
+    // pueden pasar el error por acá y seguir comparando igual que antes.
+    // ========================================================================
+    pub const fn codigo_v1(self) -> u32 {
+        match self {
+            Error::YaInicializado => Error::NoInicializado as u32,
+            error => error as u32,
+        }
+    }
+}
+
+// ============================================================================
+// ERRORES EXTRA
+// ============================================================================
+// El spec de un #[contracterror] admite como máximo 50 variantes y Error ya
+// las usa todas (#1..=#50).
+// Los códigos nuevos siguen acá, desde el #51, sin repetir ninguno de Error.
+//
+// Las funciones que pueden fallar con códigos de los dos enums retornan
+// soroban_sdk::Error: los dos se convierten con `?` o `.into()`.
+// Para quien llama no cambia nada: siempre recibe Error(Contract, #N).
+// ============================================================================
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ErrorExtra {
+    /// No hay saludo registrado para ese idioma
+    IdiomaNoSoportado = 51,
+    /// Saludo vacío o más largo que MAX_LARGO_SALUDO
+    SaludoInvalido = 52,
+    /// Se alcanzó MAX_IDIOMAS
+    DemasiadosIdiomas = 53,
+    /// No se puede quitar el idioma por defecto
+    IdiomaPorDefecto = 54,
+    /// Plantilla vacía, muy larga o con un {marcador} mal escrito
+    PlantillaInvalida = 55,
+    /// No hay plantilla con ese id
+    PlantillaNoExiste = 56,
+    /// La bio del perfil supera MAX_LARGO_BIO
+    BioMuyLarga = 57,
+    /// El usuario no creó su perfil
+    SinPerfil = 58,
+    /// Otra dirección ya registró ese nombre
+    NombreOcupado = 59,
+    /// El usuario ya tiene un nombre registrado
+    YaTieneNombre = 60,
+    /// El usuario no tiene nombre para liberar o transferir
+    SinNombreRegistrado = 61,
+    /// Duración 0 o mayor al máximo configurado
+    DuracionInvalida = 62,
+    /// No hay compromiso pendiente (o ya venció)
+    CompromisoNoExiste = 63,
+    /// Todavía no abrió la ventana de revelación
+    RevelacionTemprana = 64,
+    /// sha256(nombre || salt) no coincide
+    RevelacionInvalida = 65,
+    /// La consulta múltiple supera MAX_CONSULTA direcciones
+    ConsultaMuyGrande = 66,
+    /// Motivo vacío o más largo que MAX_LARGO_MOTIVO
+    MotivoInvalido = 67,
+    /// Retención de auditoría menor que la actual o mayor que MAX_RETENCION_AUDITORIA
+    RetencionInvalida = 68,
+    /// clientDataJSON sin el type/challenge esperados o authenticatorData inválido
+    DatosWebAuthnInvalidos = 69,
+}
+
+// ============================================================================
+// KEYS DE STORAGE
+// ============================================================================
+// #[contracttype] permite usar el enum como key de storage
+// Las variantes con datos (Address, Symbol) crean keys compuestas
+// ============================================================================
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                               // Instance: admin de la sala general
+    ContadorSaludos,                     // Instance: total de la sala general
+    UltimoSaludo(Address),               // Persistent: último nombre por usuario
+    ContadorPorUsuario(Address),         // Persistent: saludos por usuario
+    LimiteNombre,                        // Instance: límite de caracteres
+    AdminSala(Symbol),                   // Persistent: admin de cada sala
+    ContadorSala(Symbol),                // Persistent: total de cada sala
+    LimiteSala(Symbol),                  // Persistent: límite de cada sala
+    UltimoSaludoSala(Symbol, Address),   // Persistent: último nombre por sala
+    ContadorUsuarioSala(Symbol, Address), // Persistent: saludos por sala y usuario
+    Hooks,                               // Instance: Vec<Hook> registrados
+    Moderador(Address),                  // Persistent: true si es moderador
+    ModoListaPermitidos,                 // Instance: true = solo la lista saluda
+    Permitido(Address),                  // Persistent: true si está en la lista
+    Bloqueado(Address),                  // Persistent: true si está bloqueado
+    RaizMerkle,                          // Instance: raíz de la lista Merkle
+    HojaUsada(BytesN<32>, BytesN<32>),   // Persistent: (raíz, hoja) ya usada
+    NonceFirma(BytesN<32>),              // Persistent: próximo nonce por clave
+    Passkey(BytesN<65>),                 // Persistent: usuario dueño de la passkey
+    NoncePasskey(BytesN<65>),            // Persistent: próximo nonce por passkey
+    Consejo,                             // Instance: firmantes y umbral
+    ProximaPropuesta,                    // Instance: id de la próxima propuesta
+    PropuestasPendientes,                // Instance: Vec<u32> de ids abiertos
+    Propuesta(u32),                      // Persistent: cada propuesta
+    RetrasoCambios,                      // Instance: ledgers de espera (timelock)
+    ProximoCambio,                       // Instance: id del próximo cambio
+    CambiosProgramados,                  // Instance: Vec<CambioProgramado>
+    ConfigGobernanza,                    // Instance: quórum y período de voto
+    UltimaVotacion,                      // Instance: ledger de la última votación
+    ProximaVotacion,                     // Instance: id de la próxima votación
+    Votacion(u32),                       // Persistent: cada votación
+    Voto(u32, Address),                  // Persistent: true si ya votó
+    Checkpoints(Address),                // Persistent: historial de ContadorPorUsuario
+    SaludoOculto(Address),               // Persistent: true si el saludo está oculto
+    Suspendido(Address),                 // Persistent: ledger hasta el que no saluda
+    ProximaModeracion,                   // Instance: id de la próxima acción
+    Moderacion(u32),                     // Persistent: cada acción de moderación
+    LogModeracion(Address),              // Persistent: Vec<u32> de acciones por usuario
+    PalabrasProhibidas,                  // Instance: Vec<Bytes> en minúsculas
+    Saludos,                             // Instance: Map<Symbol, String> idioma → saludo
+    IdiomaPorDefecto,                    // Instance: Symbol del idioma de respaldo
+    Idioma(Address),                     // Persistent: idioma preferido del usuario
+    Plantilla(Symbol),                   // Persistent: plantilla de respuesta por id
+    Perfil(Address),                     // Persistent: perfil público del usuario
+    DuenoNombre(String),                 // Persistent: nombre normalizado → dueño
+    NombreRegistrado(Address),           // Persistent: dueño → nombre normalizado
+    RequiereNombreRegistrado,            // Instance: bool, hello exige el nombre propio
+    SaludoEfimero(Address),              // Temporary: nombre del saludo efímero
+    VenceEfimero(Address),               // Persistent: último ledger del saludo efímero
+    MaxDuracionEfimero,                  // Instance: tope de ledgers para hello_efimero
+}
+
+// ============================================================================
+// CLAVES EXTRA
+// ============================================================================
+// Igual que con Error, el spec de un #[contracttype] enum admite como máximo
+// 50 variantes y DataKey ya está lleno. Las claves nuevas van acá.
+// La clave guardada es [nombre de la variante, campos...] sin el nombre del
+// enum: una variante de DataKeyExtra NUNCA puede llamarse igual que una de
+// DataKey, o las dos leerían y escribirían la misma entrada.
+// ============================================================================
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyExtra {
+    VentanaRevelacion,                   // Instance: VentanaRevelacion
+    Compromiso(Address),                 // Temporary: compromiso sin revelar
+    Operador(Address),                   // Persistent: true si puede usar hello_lote por otros
+    ModoDesbordamiento,                  // Instance: ModoDesbordamiento de los contadores
+    VersionEsquema,                      // Instance: versión del formato del storage
+    ProximaAuditoria,                    // Instance: id de la próxima entrada de auditoría
+    Auditoria(u32),                      // Persistent: EntradaAuditoria por id
+    PrimeraAuditoria,                    // Instance: id de la entrada más vieja que sigue guardada
+    RetencionAuditoria,                  // Instance: cuántas entradas se conservan
+}
+
+// ============================================================================
+// CONSEJO DE ADMINS (MULTISIG)
+// ============================================================================
+// Con un consejo configurado, reset_contador y set_limite_nombre ya no los
+// ejecuta un solo admin: un firmante crea una propuesta, los demás la
+// aprueban y al llegar a `umbral` aprobaciones se ejecuta sola.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Consejo {
+    pub firmantes: Vec<Address>,
+    pub umbral: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccionConsejo {
+    ResetContador,
+    SetLimiteNombre(u32),
+    CambiarConsejo(Consejo),  // Cambiar firmantes y/o umbral
+    CancelarCambio(u32),      // Sacar un cambio de la cola del timelock
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EstadoPropuesta {
+    Pendiente,
+    Ejecutada,
+    Cancelada,  // Cerrada por un cambio de consejo antes de ejecutarse
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Propuesta {
+    pub id: u32,
+    pub accion: AccionConsejo,
+    pub proponente: Address,
+    pub aprobaciones: Vec<Address>,
+    pub expira: u32,  // Último ledger en el que se puede aprobar
+    pub estado: EstadoPropuesta,
+}
+
+// ============================================================================
+// HOOKS POST-SALUDO
+// ============================================================================
+// Un hook es otro contrato que implementa la interfaz SaludoHook.
+// Después de cada saludo (con el estado ya guardado) llamamos a on_hello()
+// en cada hook registrado. #[contractclient] genera SaludoHookClient para
+// que otros contratos (y los tests) puedan usar la misma interfaz.
+// ============================================================================
+#[contractclient(name = "SaludoHookClient")]
+pub trait SaludoHook {
+    fn on_hello(env: Env, usuario: Address, nombre: String, contador: u64);
+}
+
+// Configuración de cada hook registrado
+// - obligatorio = true  → si el hook falla, el saludo completo falla
+// - obligatorio = false → el fallo se ignora y se emite HookFallido
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hook {
+    pub contrato: Address,
+    pub obligatorio: bool,
+}
+
+// Evento emitido cuando un hook NO obligatorio falla
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HookFallido {
+    #[topic]
+    pub hook: Address,
+    pub usuario: Address,
+    pub contador: u64,
+}
+
+// ============================================================================
+// CAMBIOS DE CONFIGURACIÓN CON TIMELOCK
+// ============================================================================
+// Con un retraso > 0, los cambios de configuración se ponen en cola y solo
+// se pueden ejecutar cuando pasaron `retraso` ledgers. Así quien usa el
+// contrato ve venir el cambio (evento CambioEnCola) antes de que aplique.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CambioConfig {
+    LimiteNombre(u32),
+    Retraso(u32),  // El propio retraso también pasa por la cola
+}
+
+// Quién decidió el cambio: define quién lo puede cancelar
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrigenCambio {
+    Admin,     // programar_limite / programar_retraso: el admin o el consejo
+    Consejo,   // Propuesta aprobada: solo el consejo
+    Votacion,  // Votación aprobada: nadie, la comunidad ya decidió
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CambioProgramado {
+    pub id: u32,
+    pub cambio: CambioConfig,
+    pub ejecutable_desde: u32,  // Primer ledger en el que se puede ejecutar
+    pub origen: OrigenCambio,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CambioEnCola {
+    #[topic]
+    pub id: u32,
+    pub cambio: CambioConfig,
+    pub ejecutable_desde: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CambioEjecutado {
+    #[topic]
+    pub id: u32,
+    pub cambio: CambioConfig,
+}
+
+// ============================================================================
+// GOBERNANZA COMUNITARIA DEL LÍMITE DE NOMBRE
+// ============================================================================
+// Quien saludó puede proponer un nuevo límite y votarlo. El peso de cada
+// voto es su ContadorPorUsuario en el ledger en que se creó la votación:
+// saludar después de creada la votación no da más votos.
+// Una votación aprobada ya es la decisión: no pasa por el consejo, pero con
+// timelock activo el nuevo límite espera en la cola como cualquier cambio.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigGobernanza {
+    pub quorum: u64,   // Peso total mínimo (a favor + en contra)
+    pub periodo: u32,  // Ledgers que dura cada votación
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EstadoVotacion {
+    Abierta,
+    Aprobada,   // Llegó al quórum con mayoría a favor: el límite se aplicó
+    Rechazada,  // Sin quórum o sin mayoría
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Votacion {
+    pub id: u32,
+    pub proponente: Address,
+    pub limite: u32,
+    pub inicio: u32,  // Ledger de la instantánea de pesos
+    pub fin: u32,     // Último ledger en el que se puede votar
+    pub quorum: u64,  // Quórum vigente al proponer: cambiarlo después no la afecta
+    pub a_favor: u64,
+    pub en_contra: u64,
+    pub estado: EstadoVotacion,
+}
+
+// Valor de ContadorPorUsuario a partir de `ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub valor: u64,
+}
+
+// ============================================================================
+// MODERACIÓN
+// ============================================================================
+// Los moderadores pueden ocultar o borrar el UltimoSaludo de la sala general
+// y suspender usuarios por N ledgers. Cada acción queda registrada con su
+// motivo y el usuario afectado puede apelarla una sola vez.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TipoModeracion {
+    Ocultar = 1,
+    Borrar = 2,
+    Suspender = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MotivoModeracion {
+    Spam = 1,
+    Ofensivo = 2,
+    Suplantacion = 3,
+    Otro = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EstadoApelacion {
+    SinApelar = 0,
+    Pendiente = 1,
+    Aceptada = 2,   // La acción se revirtió (si era reversible)
+    Rechazada = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccionModeracion {
+    pub id: u32,
+    pub usuario: Address,
+    pub moderador: Address,
+    pub tipo: TipoModeracion,
+    pub motivo: MotivoModeracion,
+    pub ledger: u32,
+    pub hasta: u32,  // Solo para Suspender: último ledger suspendido
+    pub apelacion: EstadoApelacion,
+    pub mensaje_apelacion: Option<String>,
+}
+
+// ============================================================================
+// AUDITORÍA DE ACCIONES DEL ADMIN
+// ============================================================================
+// Cada acción privilegiada deja una entrada con quién la hizo, el valor
+// anterior, el nuevo y (en los ajustes manuales) el motivo.
+// Lo que aplica el consejo queda a nombre del firmante que completó el
+// umbral; lo que no tiene firmante (cola del timelock, votaciones) queda a
+// nombre del propio contrato.
+// El log es de solo agregar: las entradas no se modifican, y solo se
+// descartan cuando tienen más ledgers que la retención configurada.
+// Su TTL es propio (TTL_AUDITORIA), independiente del de los datos de
+// usuario, y nunca menor que la retención: una entrada que todavía se
+// muestra nunca puede estar archivada.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccionAuditoria {
+    SetContador = 0,             // set_contador()
+    AjustarContadorUsuario = 1,  // ajustar_contador_usuario()
+    BorrarUltimoSaludo = 2,      // borrar_ultimo_saludo()
+    ResetContador = 3,           // reset_contador()
+    TransferAdmin = 4,           // transfer_admin()
+    SetLimiteNombre = 5,         // set_limite_nombre()
+    SetRetencion = 6,            // set_retencion_auditoria()
+    ResetContadorConsejo = 7,    // Propuesta AccionConsejo::ResetContador
+    SetLimiteConsejo = 8,        // Propuesta AccionConsejo::SetLimiteNombre sin timelock
+    CambiarConsejo = 9,          // Propuesta AccionConsejo::CambiarConsejo
+    LimitePorTimelock = 10,      // ejecutar_cambio() de un LimiteNombre
+    RetrasoPorTimelock = 11,     // ejecutar_cambio() de un Retraso
+    LimitePorVotacion = 12,      // finalizar_votacion() aprobada sin timelock
+    RegistrarHook = 13,          // registrar_hook()
+    QuitarHook = 14,             // quitar_hook()
+    ConfigurarConsejo = 15,      // configurar_consejo()
+    AgregarModerador = 16,       // agregar_moderador()
+    QuitarModerador = 17,        // quitar_moderador()
+    SetRaizMerkle = 18,          // set_raiz_merkle()
+    SetModoDesbordamiento = 19,  // set_modo_desbordamiento()
+    CrearSala = 20,              // crear_sala()
+    ResetContadorSala = 21,      // reset_contador_sala()
+    SetLimiteSala = 22,          // set_limite_sala()
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValorAuditoria {
+    Ninguno,         // No había valor (o dejó de haberlo)
+    Numero(u64),
+    Texto(String),
+    Direccion(Address),
+    Booleano(bool),
+    Hash(BytesN<32>),
+    Consejo(Consejo),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntradaAuditoria {
+    pub id: u32,
+    pub actor: Address,              // Quién hizo el cambio (ver arriba)
+    pub accion: AccionAuditoria,
+    pub usuario: Option<Address>,    // None si el cambio no es sobre un usuario
+    pub sala: Option<Symbol>,        // None si el cambio no es sobre una sala creada
+    pub anterior: ValorAuditoria,
+    pub nuevo: ValorAuditoria,
+    pub motivo: Option<String>,  // None en las acciones que no piden motivo
+    pub ledger: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auditado {
+    #[topic]
+    pub accion: AccionAuditoria,
+    #[topic]
+    pub id: u32,
+    pub actor: Address,
+    pub usuario: Option<Address>,
+    pub sala: Option<Symbol>,
+    pub anterior: ValorAuditoria,
+    pub nuevo: ValorAuditoria,
+}
+
+// ============================================================================
+// PERFILES
+// ============================================================================
+// Un perfil por dirección. hello_perfil() saluda usando `nombre` y el
+// idioma del perfil es el mismo que se elige con set_idioma().
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Perfil {
+    pub nombre: String,               // Nombre visible (mismo límite que hello)
+    pub bio: String,                  // Hasta MAX_LARGO_BIO bytes, puede estar vacía
+    pub avatar: Option<BytesN<32>>,   // Hash del avatar (el archivo vive fuera de la cadena)
+    pub idioma: Option<Symbol>,       // Idioma preferido para las respuestas
+    pub creado: u32,                  // Ledger en el que se creó el perfil
+}
+
+// ============================================================================
+// SALUDOS EFÍMEROS
+// ============================================================================
+// El nombre vive en temporary storage y desaparece solo. El ledger de
+// vencimiento queda en persistent para poder responder "ya venció" en vez
+// de "nunca saludó" después de que el temporary se borra.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EstadoEfimero {
+    NuncaSaludo,
+    Vencido,
+    Vigente(String),
+}
+
+// ============================================================================
+// COMPROMISO Y REVELACIÓN
+// ============================================================================
+// comprometer() guarda sha256(nombre || salt) y revelar() recién saluda
+// dentro de la ventana [ledger + espera, ledger + espera + duracion].
+// El compromiso vive en temporary: si nadie lo revela, vence solo.
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VentanaRevelacion {
+    pub espera: u32,    // Ledgers entre comprometer y poder revelar
+    pub duracion: u32,  // Ledgers que la ventana queda abierta
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Compromiso {
+    pub hash: BytesN<32>,
+    pub desde: u32,  // Primer ledger en el que se puede revelar
+    pub hasta: u32,  // Último ledger en el que se puede revelar
+}
+
+// ============================================================================
+// INFORMACIÓN DEL CONTRATO
+// ============================================================================
+// get_info() le cuenta a las herramientas qué variante está desplegada:
+// versión, funcionalidades, versión del esquema de storage y códigos de error
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Version {
+    pub mayor: u32,
+    pub menor: u32,
+    pub parche: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodigoError {
+    pub codigo: u32,
+    pub nombre: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InfoContrato {
+    pub version: Version,
+    pub funcionalidades: Vec<Symbol>,  // Lo que este WASM sabe hacer
+    pub activas: Vec<Symbol>,          // Modos opcionales encendidos en esta instancia
+    pub version_esquema: u32,          // Cambia cuando cambia el formato del storage
+    pub errores: Vec<CodigoError>,     // Error y ErrorExtra, ordenados por código
+}
+
+// ============================================================================
+// DESBORDAMIENTO DE CONTADORES
+// ============================================================================
+// Qué pasa cuando un contador (u64) ya está en u64::MAX y alguien saluda
+// ============================================================================
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ModoDesbordamiento {
+    Fallar = 0,   // El saludo falla con Error::ContadorDesbordado (por defecto)
+    Saturar = 1,  // El saludo se registra y el contador queda en u64::MAX
+}
+
+// ============================================================================
+// CONSTANTES
+// ============================================================================
+// SALA_GENERAL es la sala que usan las funciones "legacy" (hello,
+// get_contador, reset_contador...). Sus datos viven en las keys originales
+// (Admin, ContadorSaludos, UltimoSaludo...) para que un contrato ya desplegado
+// siga funcionando igual después de agregar salas.
+// ============================================================================
+pub const SALA_GENERAL: Symbol = symbol_short!("general");
+const LIMITE_POR_DEFECTO: u32 = 32;
+const LIMITE_MAXIMO: u32 = 256;
+const MAX_HOOKS: u32 = 5;
+const MAX_LOTE: u32 = 50;
+const LOTE_OK: u32 = 0;  // Código de hello_lote() para una entrada exitosa
+const MAX_CONSULTA: u32 = 100;  // Direcciones por consulta múltiple (solo lectura)
+
+// Para get_info(): la versión tiene que coincidir con contractmeta!
+const VERSION_CONTRATO: Version = Version { mayor: 2, menor: 0, parche: 0 };
+// Esquema 1: contadores u32. Esquema 2: contadores u64 (ver migrar_esquema)
+const VERSION_ESQUEMA: u32 = 2;
+
+const FUNCIONALIDADES: [&str; 22] = [
+    "salas", "hooks", "moderadores", "lista_permitidos", "merkle",
+    "firma_ed25519", "passkeys", "consejo", "timelock", "gobernanza",
+    "moderacion", "palabras_prohibidas", "idiomas", "plantillas", "perfiles",
+    "registro_nombres", "efimeros", "compromisos", "auditoria", "lote",
+    "consultas_multiples", "saturar_contadores",
+];
+
+// Una fila por variante de Error y ErrorExtra (test_info_tabla_errores
+// verifica que no falte ninguna)
+const TABLA_ERRORES: [(u32, &str); 69] = [
+    (Error::NombreVacio as u32, "NombreVacio"),
+    (Error::NombreMuyLargo as u32, "NombreMuyLargo"),
+    (Error::NoAutorizado as u32, "NoAutorizado"),
+    (Error::NoInicializado as u32, "NoInicializado"),
+    (Error::LimiteInvalido as u32, "LimiteInvalido"),
+    (Error::SalaYaExiste as u32, "SalaYaExiste"),
+    (Error::SalaNoExiste as u32, "SalaNoExiste"),
+    (Error::HookFallido as u32, "HookFallido"),
+    (Error::DemasiadosHooks as u32, "DemasiadosHooks"),
+    (Error::HookNoExiste as u32, "HookNoExiste"),
+    (Error::UsuarioBloqueado as u32, "UsuarioBloqueado"),
+    (Error::NoEstaEnLista as u32, "NoEstaEnLista"),
+    (Error::LoteMuyGrande as u32, "LoteMuyGrande"),
+    (Error::RaizNoConfigurada as u32, "RaizNoConfigurada"),
+    (Error::PruebaInvalida as u32, "PruebaInvalida"),
+    (Error::HojaYaUsada as u32, "HojaYaUsada"),
+    (Error::FirmaExpirada as u32, "FirmaExpirada"),
+    (Error::NonceInvalido as u32, "NonceInvalido"),
+    (Error::PasskeyYaRegistrada as u32, "PasskeyYaRegistrada"),
+    (Error::PasskeyNoRegistrada as u32, "PasskeyNoRegistrada"),
+    (Error::ConsejoInvalido as u32, "ConsejoInvalido"),
+    (Error::RequiereConsejo as u32, "RequiereConsejo"),
+    (Error::PropuestaNoExiste as u32, "PropuestaNoExiste"),
+    (Error::PropuestaExpirada as u32, "PropuestaExpirada"),
+    (Error::PropuestaCerrada as u32, "PropuestaCerrada"),
+    (Error::YaAprobada as u32, "YaAprobada"),
+    (Error::DemasiadasPropuestas as u32, "DemasiadasPropuestas"),
+    (Error::RequiereProgramacion as u32, "RequiereProgramacion"),
+    (Error::CambioNoExiste as u32, "CambioNoExiste"),
+    (Error::CambioNoListo as u32, "CambioNoListo"),
+    (Error::DemasiadosCambios as u32, "DemasiadosCambios"),
+    (Error::RetrasoInvalido as u32, "RetrasoInvalido"),
+    (Error::VotacionNoExiste as u32, "VotacionNoExiste"),
+    (Error::VotacionCerrada as u32, "VotacionCerrada"),
+    (Error::YaVoto as u32, "YaVoto"),
+    (Error::SinPesoDeVoto as u32, "SinPesoDeVoto"),
+    (Error::VotacionAbierta as u32, "VotacionAbierta"),
+    (Error::GobernanzaInvalida as u32, "GobernanzaInvalida"),
+    (Error::UsuarioSuspendido as u32, "UsuarioSuspendido"),
+    (Error::AccionNoExiste as u32, "AccionNoExiste"),
+    (Error::YaApelada as u32, "YaApelada"),
+    (Error::ApelacionNoPendiente as u32, "ApelacionNoPendiente"),
+    (Error::SinSaludo as u32, "SinSaludo"),
+    (Error::ApelacionInvalida as u32, "ApelacionInvalida"),
+    (Error::NombreProhibido as u32, "NombreProhibido"),
+    (Error::PalabraInvalida as u32, "PalabraInvalida"),
+    (Error::DemasiadasPalabras as u32, "DemasiadasPalabras"),
+    (Error::NombreNoCoincide as u32, "NombreNoCoincide"),
+    (Error::YaInicializado as u32, "YaInicializado"),
+    (Error::ContadorDesbordado as u32, "ContadorDesbordado"),
+    (ErrorExtra::IdiomaNoSoportado as u32, "IdiomaNoSoportado"),
+    (ErrorExtra::SaludoInvalido as u32, "SaludoInvalido"),
+    (ErrorExtra::DemasiadosIdiomas as u32, "DemasiadosIdiomas"),
+    (ErrorExtra::IdiomaPorDefecto as u32, "IdiomaPorDefecto"),
+    (ErrorExtra::PlantillaInvalida as u32, "PlantillaInvalida"),
+    (ErrorExtra::PlantillaNoExiste as u32, "PlantillaNoExiste"),
+    (ErrorExtra::BioMuyLarga as u32, "BioMuyLarga"),
+    (ErrorExtra::SinPerfil as u32, "SinPerfil"),
+    (ErrorExtra::NombreOcupado as u32, "NombreOcupado"),
+    (ErrorExtra::YaTieneNombre as u32, "YaTieneNombre"),
+    (ErrorExtra::SinNombreRegistrado as u32, "SinNombreRegistrado"),
+    (ErrorExtra::DuracionInvalida as u32, "DuracionInvalida"),
+    (ErrorExtra::CompromisoNoExiste as u32, "CompromisoNoExiste"),
+    (ErrorExtra::RevelacionTemprana as u32, "RevelacionTemprana"),
+    (ErrorExtra::RevelacionInvalida as u32, "RevelacionInvalida"),
+    (ErrorExtra::ConsultaMuyGrande as u32, "ConsultaMuyGrande"),
+    (ErrorExtra::MotivoInvalido as u32, "MotivoInvalido"),
+    (ErrorExtra::RetencionInvalida as u32, "RetencionInvalida"),
+    (ErrorExtra::DatosWebAuthnInvalidos as u32, "DatosWebAuthnInvalidos"),
+];
+const MAX_PRUEBA: u32 = 32;  // Profundidad máxima: árboles de hasta 2^32 hojas
+const DOMINIO_HELLO_FIRMADO: &[u8] = b"hello-tiburona:hello_firmado:v1";
+const DOMINIO_HELLO_PASSKEY: &[u8] = b"hello-tiburona:hello_passkey:v1";
+const MIN_DATOS_AUTENTICADOR: u32 = 37;  // rpIdHash (32) + flags (1) + signCount (4)
+const MAX_CLIENT_DATA_JSON: u32 = 1024;
+const BANDERA_USUARIO_PRESENTE: u8 = 0x01;  // Bit UP de los flags de authenticatorData
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const MAX_FIRMANTES: u32 = 10;
+const MAX_PROPUESTAS: u32 = 20;
+const MAX_CAMBIOS: u32 = 10;
+const MAX_RETRASO: u32 = 535_680;  // ~1 mes de ledgers de 5 segundos
+const QUORUM_POR_DEFECTO: u64 = 10;
+const PERIODO_POR_DEFECTO: u32 = 17_280;  // ~1 día
+const SALUDO_OCULTO: &str = "[oculto]";
+const MAX_APELACION: u32 = 256;
+const MAX_LARGO_MOTIVO: u32 = 256;
+// Retención en ledgers: una entrada se muestra mientras sea más nueva que esto
+const RETENCION_AUDITORIA_POR_DEFECTO: u32 = 535_680;  // ~1 mes
+const MAX_RETENCION_AUDITORIA: u32 = 3_110_400;        // ~6 meses
+const MAX_PODA_AUDITORIA: u32 = 5;  // Entradas vencidas borradas por cada entrada nueva
+// Cada entrada vive el máximo de retención (los datos de usuario usan 100):
+// así cubre cualquier retención, también si se sube después de escribirla
+const TTL_AUDITORIA: u32 = MAX_RETENCION_AUDITORIA;
+// Filtro de palabras: lista acotada para que el costo de hello() también lo sea
+// Peor caso: 20 palabras × 256 posiciones × 32 bytes comparados
+// (~18M instrucciones en WASM, ver test_palabras_prohibidas_costo_acotado)
+const MAX_PALABRAS_PROHIBIDAS: u32 = 20;
+const MAX_LARGO_PALABRA: u32 = 32;
+// Saludos localizados: "es" → "Hola" existe desde el despliegue
+const IDIOMA_ORIGINAL: Symbol = symbol_short!("es");
+const SALUDO_ORIGINAL: &str = "Hola";
+const MAX_IDIOMAS: u32 = 20;
+const MAX_LARGO_SALUDO: u32 = 32;
+// Plantillas: "Hola {nombre}, eres la saludadora #{contador}"
+const MAX_LARGO_PLANTILLA: u32 = 128;
+const MAX_LARGO_BIO: u32 = 280;
+const MAX_DURACION_EFIMERO_POR_DEFECTO: u32 = 17_280;  // ~1 día
+// max_entry_ttl de mainnet (~6 meses): un TTL mayor no se puede pedir
+const MAX_DURACION_EFIMERO: u32 = 3_110_400;
+const ESPERA_REVELACION_POR_DEFECTO: u32 = 10;
+const DURACION_REVELACION_POR_DEFECTO: u32 = 17_280;   // ~1 día
+
+// ============================================================================
+// DEFINICIÓN DEL CONTRATO
+// ============================================================================
+// #[contract] marca la struct como contrato de Soroban
+// La struct no guarda datos: todo el estado vive en storage
+// ============================================================================
+#[contract]
+pub struct HelloContract;
+
+// ============================================================================
+// HELPERS DE SALAS
+// ============================================================================
+// Funciones internas (no son endpoints) que traducen "sala" → key de storage.
+// La sala general usa instance storage con las keys legacy; las demás salas
+// usan persistent storage con keys compuestas por el id de la sala.
+// ============================================================================
+
+fn es_general(sala: &Symbol) -> bool {
+    *sala == SALA_GENERAL
+}
+
+fn leer_admin(env: &Env, sala: &Symbol) -> Option<Address> {
+    if es_general(sala) {
+        env.storage().instance().get(&DataKey::Admin)
+    } else {
+        env.storage().persistent().get(&DataKey::AdminSala(sala.clone()))
+    }
+}
+
+// Los contadores se guardaban como u32 (esquema 1) y ahora son u64.
+// Al leer se aceptan los dos; al escribir siempre se guarda u64, así cada
+// entrada vieja se migra sola la próxima vez que se escribe.
+fn contador_desde_val(env: &Env, valor: Option<Val>) -> u64 {
+    match valor {
+        None => 0,
+        Some(valor) => u64::try_from_val(env, &valor)
+            .or_else(|_| u32::try_from_val(env, &valor).map(u64::from))
+            .unwrap_or(0),
+    }
+}
+
+fn leer_contador(env: &Env, sala: &Symbol) -> u64 {
+    let valor: Option<Val> = if es_general(sala) {
+        env.storage().instance().get(&DataKey::ContadorSaludos)
+    } else {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContadorSala(sala.clone()))
+    };
+    contador_desde_val(env, valor)
+}
+
+fn leer_contador_usuario(env: &Env, sala: &Symbol, usuario: &Address) -> u64 {
+    let valor: Option<Val> = env.storage()
+        .persistent()
+        .get(&key_contador_usuario(sala, usuario.clone()));
+    contador_desde_val(env, valor)
+}
+
+fn leer_modo_desbordamiento(env: &Env) -> ModoDesbordamiento {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::ModoDesbordamiento)
+        .unwrap_or(ModoDesbordamiento::Fallar)
+}
+
+// valor + 1 sin panic: u64::MAX falla o se queda igual según el modo
+fn incrementar(env: &Env, valor: u64) -> Result<u64, Error> {
+    match valor.checked_add(1) {
+        Some(siguiente) => Ok(siguiente),
+        None => match leer_modo_desbordamiento(env) {
+            ModoDesbordamiento::Saturar => Ok(u64::MAX),
+            ModoDesbordamiento::Fallar => Err(Error::ContadorDesbordado),
+        },
+    }
+}
+
+// Sin la clave, el contrato es anterior al esquema 2
+fn leer_version_esquema(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::VersionEsquema)
+        .unwrap_or(1)
+}
+
+fn guardar_contador(env: &Env, sala: &Symbol, valor: u64) {
+    if es_general(sala) {
+        env.storage().instance().set(&DataKey::ContadorSaludos, &valor);
+    } else {
+        let key = DataKey::ContadorSala(sala.clone());
+        env.storage().persistent().set(&key, &valor);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
+
+fn leer_limite(env: &Env, sala: &Symbol) -> u32 {
+    if es_general(sala) {
+        env.storage()
+            .instance()
+            .get(&DataKey::LimiteNombre)
+            .unwrap_or(LIMITE_POR_DEFECTO)
+    } else {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LimiteSala(sala.clone()))
+            .unwrap_or(LIMITE_POR_DEFECTO)
+    }
+}
+
+fn guardar_limite(env: &Env, sala: &Symbol, limite: u32) {
+    if es_general(sala) {
+        env.storage().instance().set(&DataKey::LimiteNombre, &limite);
+    } else {
+        let key = DataKey::LimiteSala(sala.clone());
+        env.storage().persistent().set(&key, &limite);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
+
+fn key_ultimo_saludo(sala: &Symbol, usuario: Address) -> DataKey {
+    if es_general(sala) {
+        DataKey::UltimoSaludo(usuario)
+    } else {
+        DataKey::UltimoSaludoSala(sala.clone(), usuario)
+    }
+}
+
+fn key_contador_usuario(sala: &Symbol, usuario: Address) -> DataKey {
+    if es_general(sala) {
+        DataKey::ContadorPorUsuario(usuario)
+    } else {
+        DataKey::ContadorUsuarioSala(sala.clone(), usuario)
+    }
+}
+
+fn leer_hooks(env: &Env) -> Vec<Hook> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Hooks)
+        .unwrap_or(Vec::new(env))
+}
+
+// Llama a on_hello() en cada hook registrado
+// try_invoke_contract NO hace panic si el hook falla: retorna Err y
+// decidimos según la configuración del hook
+fn ejecutar_hooks(
+    env: &Env,
+    usuario: &Address,
+    nombre: &String,
+    contador: u64
+) -> Result<(), Error> {
+    let func = Symbol::new(env, "on_hello");
+
+    for hook in leer_hooks(env).iter() {
+        let args = vec![
+            env,
+            usuario.into_val(env),
+            nombre.into_val(env),
+            contador.into_val(env),
+        ];
+        let resultado = env.try_invoke_contract::<(), InvokeError>(&hook.contrato, &func, args);
+
+        // Ok(Ok(())) = el hook terminó bien; cualquier otra cosa es un fallo
+        if !matches!(resultado, Ok(Ok(()))) {
+            if hook.obligatorio {
+                return Err(Error::HookFallido);
+            }
+            HookFallido {
+                hook: hook.contrato.clone(),
+                usuario: usuario.clone(),
+                contador,
+            }
+            .publish(env);
+        }
+    }
+
+    Ok(())
+}
+
+fn validar_limite(limite: u32) -> Result<(), Error> {
+    if limite == 0 || limite > LIMITE_MAXIMO {
+        return Err(Error::LimiteInvalido);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DEL FILTRO DE PALABRAS
+// ============================================================================
+
+fn leer_palabras_prohibidas(env: &Env) -> Vec<Bytes> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PalabrasProhibidas)
+        .unwrap_or(Vec::new(env))
+}
+
+// Copia la palabra a minúsculas ASCII (los bytes no ASCII quedan igual)
+fn normalizar_palabra(env: &Env, palabra: &Bytes) -> Result<Bytes, Error> {
+    if palabra.is_empty() || palabra.len() > MAX_LARGO_PALABRA {
+        return Err(Error::PalabraInvalida);
+    }
+
+    let mut normalizada = Bytes::new(env);
+    for byte in palabra.iter() {
+        normalizada.push_back(byte.to_ascii_lowercase());
+    }
+    Ok(normalizada)
+}
+
+// Se llama DESPUÉS de validar el largo: `nombre` tiene a lo sumo LIMITE_MAXIMO bytes
+fn verificar_palabras(env: &Env, nombre: &String) -> Result<(), Error> {
+    let palabras = leer_palabras_prohibidas(env);
+    if palabras.is_empty() {
+        return Ok(());
+    }
+
+    let largo = nombre.len() as usize;
+    let mut buffer = [0u8; LIMITE_MAXIMO as usize];
+    nombre.copy_into_slice(&mut buffer[..largo]);
+    let texto = &mut buffer[..largo];
+    texto.make_ascii_lowercase();
+
+    let mut patron = [0u8; MAX_LARGO_PALABRA as usize];
+    for palabra in palabras.iter() {
+        let largo_patron = palabra.len() as usize;
+        palabra.copy_into_slice(&mut patron[..largo_patron]);
+
+        if texto.windows(largo_patron).any(|ventana| ventana == &patron[..largo_patron]) {
+            return Err(Error::NombreProhibido);
+        }
+    }
+    Ok(())
+}
+
+// Verifica que `caller` sea el admin de la sala
+// - Sala general sin admin → NoInicializado (comportamiento original)
+// - Sala con nombre sin admin → SalaNoExiste
+fn verificar_admin(env: &Env, sala: &Symbol, caller: &Address) -> Result<(), Error> {
+    let admin = match leer_admin(env, sala) {
+        Some(admin) => admin,
+        None if es_general(sala) => return Err(Error::NoInicializado),
+        None => return Err(Error::SalaNoExiste),
+    };
+
+    if *caller != admin {
+        return Err(Error::NoAutorizado);
+    }
+
+    // Pasar la dirección del admin no alcanza: el admin tiene que firmar
+    caller.require_auth();
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DEL REGISTRO DE NOMBRES
+// ============================================================================
+
+// Minúsculas ASCII: "Ana", "ANA" y "ana" son el mismo nombre registrado
+// Se llama DESPUÉS de validar el largo: `nombre` tiene a lo sumo LIMITE_MAXIMO bytes
+fn normalizar_nombre(env: &Env, nombre: &String) -> String {
+    let largo = nombre.len() as usize;
+    let mut buffer = [0u8; LIMITE_MAXIMO as usize];
+    nombre.copy_into_slice(&mut buffer[..largo]);
+    buffer[..largo].make_ascii_lowercase();
+    String::from_bytes(env, &buffer[..largo])
+}
+
+fn leer_nombre_registrado(env: &Env, usuario: &Address) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::NombreRegistrado(usuario.clone()))
+}
+
+fn asignar_nombre(env: &Env, nombre: &String, dueno: &Address) {
+    let key_dueno = DataKey::DuenoNombre(nombre.clone());
+    let key_nombre = DataKey::NombreRegistrado(dueno.clone());
+
+    env.storage().persistent().set(&key_dueno, dueno);
+    env.storage().persistent().set(&key_nombre, nombre);
+    env.storage().persistent().extend_ttl(&key_dueno, 100, 100);
+    env.storage().persistent().extend_ttl(&key_nombre, 100, 100);
+}
+
+// Con RequiereNombreRegistrado activo solo se saluda con el nombre propio
+fn verificar_nombre_registrado(env: &Env, usuario: &Address, nombre: &String) -> Result<(), Error> {
+    let requerido: bool = env.storage()
+        .instance()
+        .get(&DataKey::RequiereNombreRegistrado)
+        .unwrap_or(false);
+    if !requerido {
+        return Ok(());
+    }
+
+    match leer_nombre_registrado(env, usuario) {
+        Some(registrado) if registrado == normalizar_nombre(env, nombre) => Ok(()),
+        _ => Err(Error::NombreNoCoincide),
+    }
+}
+
+// ============================================================================
+// HELPERS DE COMPROMISO Y REVELACIÓN
+// ============================================================================
+
+fn leer_ventana_revelacion(env: &Env) -> VentanaRevelacion {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::VentanaRevelacion)
+        .unwrap_or(VentanaRevelacion {
+            espera: ESPERA_REVELACION_POR_DEFECTO,
+            duracion: DURACION_REVELACION_POR_DEFECTO,
+        })
+}
+
+// Un compromiso cuya ventana ya cerró cuenta como inexistente
+fn leer_compromiso(env: &Env, usuario: &Address) -> Option<Compromiso> {
+    let compromiso: Option<Compromiso> = env.storage()
+        .temporary()
+        .get(&DataKeyExtra::Compromiso(usuario.clone()));
+
+    compromiso.filter(|c| env.ledger().sequence() <= c.hasta)
+}
+
+// sha256(nombre || salt), lo mismo que calcula quien se compromete
+fn hash_compromiso(env: &Env, nombre: &String, salt: &Bytes) -> BytesN<32> {
+    let mut datos = nombre.to_bytes();
+    datos.append(salt);
+    env.crypto().sha256(&datos).to_bytes()
+}
+
+// ============================================================================
+// HELPERS DE IDIOMAS
+// ============================================================================
+
+// Saludo por idioma; si el admin nunca registró nada, solo existe "es" → "Hola"
+fn leer_saludos(env: &Env) -> Map<Symbol, String> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Saludos)
+        .unwrap_or_else(|| {
+            let mut saludos = Map::new(env);
+            saludos.set(IDIOMA_ORIGINAL, String::from_str(env, SALUDO_ORIGINAL));
+            saludos
+        })
+}
+
+fn leer_idioma_por_defecto(env: &Env) -> Symbol {
+    env.storage()
+        .instance()
+        .get(&DataKey::IdiomaPorDefecto)
+        .unwrap_or(IDIOMA_ORIGINAL)
+}
+
+// Idioma del usuario → idioma por defecto → "Hola"
+// El idioma del usuario puede haber sido quitado después de elegirlo
+fn saludo_para(env: &Env, usuario: &Address) -> String {
+    let saludos = leer_saludos(env);
+
+    let preferido: Option<Symbol> = env.storage()
+        .persistent()
+        .get(&DataKey::Idioma(usuario.clone()));
+
+    preferido
+        .and_then(|idioma| saludos.get(idioma))
+        .or_else(|| saludos.get(leer_idioma_por_defecto(env)))
+        .unwrap_or_else(|| String::from_str(env, SALUDO_ORIGINAL))
+}
+
+// Única forma de cambiar el idioma: mantiene sincronizados la preferencia
+// que usa saludo_para() y el campo `idioma` del perfil
+fn guardar_idioma(env: &Env, usuario: &Address, idioma: Option<Symbol>) -> Result<(), ErrorExtra> {
+    let key = DataKey::Idioma(usuario.clone());
+
+    match &idioma {
+        Some(codigo) => {
+            if !leer_saludos(env).contains_key(codigo.clone()) {
+                return Err(ErrorExtra::IdiomaNoSoportado);
+            }
+            env.storage().persistent().set(&key, codigo);
+            env.storage().persistent().extend_ttl(&key, 100, 100);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    let key_perfil = DataKey::Perfil(usuario.clone());
+    let perfil: Option<Perfil> = env.storage().persistent().get(&key_perfil);
+    if let Some(mut perfil) = perfil {
+        perfil.idioma = idioma;
+        env.storage().persistent().set(&key_perfil, &perfil);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DE PLANTILLAS
+// ============================================================================
+// Marcadores válidos: {nombre}, {contador} (saludos totales) y
+// {usuario_contador} (saludos del usuario). Cualquier otra `{` o `}` suelta
+// hace que la plantilla sea inválida, así que al renderizar no hay sorpresas.
+// ============================================================================
+
+#[derive(Clone, Copy)]
+enum Marcador {
+    Nombre,
+    Contador,
+    UsuarioContador,
+}
+
+const MARCADORES: [(&[u8], Marcador); 3] = [
+    (b"{nombre}", Marcador::Nombre),
+    (b"{contador}", Marcador::Contador),
+    (b"{usuario_contador}", Marcador::UsuarioContador),
+];
+
+// Si `texto` empieza con un marcador, retorna cuál es y cuántos bytes ocupa
+fn marcador_al_inicio(texto: &[u8]) -> Option<(Marcador, usize)> {
+    MARCADORES
+        .iter()
+        .find(|(patron, _)| texto.starts_with(patron))
+        .map(|(patron, marcador)| (*marcador, patron.len()))
+}
+
+fn validar_plantilla(plantilla: &String) -> Result<(), ErrorExtra> {
+    let largo = plantilla.len() as usize;
+    if largo == 0 || largo > MAX_LARGO_PLANTILLA as usize {
+        return Err(ErrorExtra::PlantillaInvalida);
+    }
+
+    let mut buffer = [0u8; MAX_LARGO_PLANTILLA as usize];
+    plantilla.copy_into_slice(&mut buffer[..largo]);
+    let texto = &buffer[..largo];
+
+    let mut i = 0;
+    while i < largo {
+        match texto[i] {
+            b'{' => match marcador_al_inicio(&texto[i..]) {
+                Some((_, saltar)) => i += saltar,
+                None => return Err(ErrorExtra::PlantillaInvalida),
+            },
+            b'}' => return Err(ErrorExtra::PlantillaInvalida),
+            _ => i += 1,
+        }
+    }
+    Ok(())
+}
+
+// u64 en decimal, sin alloc (no_std)
+fn agregar_numero(salida: &mut Bytes, mut numero: u64) {
+    let mut digitos = [0u8; 20];
+    let mut inicio = digitos.len();
+    loop {
+        inicio -= 1;
+        digitos[inicio] = b'0' + (numero % 10) as u8;
+        numero /= 10;
+        if numero == 0 {
+            break;
+        }
+    }
+    salida.extend_from_slice(&digitos[inicio..]);
+}
+
+// La plantilla ya pasó por validar_plantilla() al guardarse
+fn renderizar_plantilla(
+    env: &Env,
+    plantilla: &String,
+    nombre: &String,
+    contador: u64,
+    usuario_contador: u64
+) -> String {
+    let largo = plantilla.len() as usize;
+    let mut buffer = [0u8; MAX_LARGO_PLANTILLA as usize];
+    plantilla.copy_into_slice(&mut buffer[..largo]);
+    let texto = &buffer[..largo];
+
+    let mut salida = Bytes::new(env);
+    let mut i = 0;
+    while i < largo {
+        match marcador_al_inicio(&texto[i..]) {
+            Some((marcador, saltar)) => {
+                match marcador {
+                    Marcador::Nombre => salida.append(&nombre.to_bytes()),
+                    Marcador::Contador => agregar_numero(&mut salida, contador),
+                    Marcador::UsuarioContador => agregar_numero(&mut salida, usuario_contador),
+                }
+                i += saltar;
+            }
+            None => {
+                salida.push_back(texto[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from(salida)
+}
+
+// ============================================================================
+// HELPERS DE MODERACIÓN
+// ============================================================================
+// Moderadores y listas de acceso son globales (aplican a todas las salas).
+// Las marcas por dirección viven en persistent storage: una entrada por
+// usuario en vez de un Vec gigante que habría que leer completo.
+// ============================================================================
+
+fn es_moderador(env: &Env, usuario: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Moderador(usuario.clone()))
+        .unwrap_or(false)
+}
+
+// El admin general siempre cuenta como moderador
+fn verificar_moderador(env: &Env, caller: &Address) -> Result<(), Error> {
+    if es_moderador(env, caller) {
+        caller.require_auth();
+        return Ok(());
+    }
+    verificar_admin(env, &SALA_GENERAL, caller)
+}
+
+// Guardia de inicialización para todo lo que lee o escribe estado
+// Las funciones solo-admin ya la tienen a través de verificar_admin().
+// Los getters también fallan con NoInicializado: un 0 o un None antes de
+// initialize() parecería un contrato en uso sin datos.
+// Solo quedan afuera get_payload_firma y get_challenge_passkey, que no
+// leen storage: arman el mensaje a firmar a partir de sus parámetros.
+fn verificar_inicializado(env: &Env) -> Result<(), Error> {
+    if !env.storage().instance().has(&DataKey::Admin) {
+        return Err(Error::NoInicializado);
+    }
+    Ok(())
+}
+
+fn marcar<K: IntoVal<Env, Val>>(env: &Env, key: &K, valor: bool) {
+    if valor {
+        env.storage().persistent().set(key, &true);
+        env.storage().persistent().extend_ttl(key, 100, 100);
+    } else {
+        // Quitar la marca libera el storage en vez de guardar `false`
+        env.storage().persistent().remove(key);
+    }
+}
+
+fn validar_lote(usuarios: &Vec<Address>) -> Result<(), Error> {
+    if usuarios.len() > MAX_LOTE {
+        return Err(Error::LoteMuyGrande);
+    }
+    Ok(())
+}
+
+// Verifica bloqueo y modo lista para quien intenta saludar
+// - membresia_probada = true cuando la pertenencia ya se verificó por otro
+//   medio (prueba Merkle), así que solo se revisa el bloqueo
+fn verificar_acceso(
+    env: &Env,
+    usuario: &Address,
+    membresia_probada: bool
+) -> Result<(), Error> {
+    let storage = env.storage().persistent();
+
+    if storage.has(&DataKey::Bloqueado(usuario.clone())) {
+        return Err(Error::UsuarioBloqueado);
+    }
+
+    let suspendido_hasta: Option<u32> = storage.get(&DataKey::Suspendido(usuario.clone()));
+    if suspendido_hasta.is_some_and(|hasta| env.ledger().sequence() <= hasta) {
+        return Err(Error::UsuarioSuspendido);
+    }
+
+    let modo_lista: bool = env.storage()
+        .instance()
+        .get(&DataKey::ModoListaPermitidos)
+        .unwrap_or(false);
+
+    if modo_lista
+        && !membresia_probada
+        && !storage.has(&DataKey::Permitido(usuario.clone()))
+    {
+        return Err(Error::NoEstaEnLista);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS MERKLE
+// ============================================================================
+// Hoja = sha256(XDR de la dirección)
+// Nodo = sha256(menor || mayor): ordenar el par evita guardar en la prueba
+// si cada hermano va a la izquierda o a la derecha
+// ============================================================================
+
+fn hoja_merkle(env: &Env, usuario: &Address) -> BytesN<32> {
+    env.crypto().sha256(&usuario.clone().to_xdr(env)).to_bytes()
+}
+
+fn hash_par(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (menor, mayor) = if a <= b { (a, b) } else { (b, a) };
+    let mut datos = Bytes::from(menor);
+    datos.append(&Bytes::from(mayor));
+    env.crypto().sha256(&datos).to_bytes()
+}
+
+fn verificar_prueba(
+    env: &Env,
+    raiz: &BytesN<32>,
+    hoja: &BytesN<32>,
+    prueba: &Vec<BytesN<32>>
+) -> Result<(), Error> {
+    if prueba.len() > MAX_PRUEBA {
+        return Err(Error::PruebaInvalida);
+    }
+
+    let mut actual = hoja.clone();
+    for hermano in prueba.iter() {
+        actual = hash_par(env, &actual, &hermano);
+    }
+
+    if actual != *raiz {
+        return Err(Error::PruebaInvalida);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DE SALUDOS FIRMADOS
+// ============================================================================
+// El payload firmado incluye un prefijo de dominio, la red y la dirección
+// de este contrato: una firma no sirve en otro contrato, en otra red ni
+// para otro mensaje que use la misma clave.
+// ============================================================================
+
+fn payload_hello_firmado(
+    env: &Env,
+    pubkey: &BytesN<32>,
+    nombre: &String,
+    nonce: u64,
+    expiracion: u32
+) -> Bytes {
+    let mut payload = Bytes::from_slice(env, DOMINIO_HELLO_FIRMADO);
+    payload.append(&Bytes::from(env.ledger().network_id()));
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&Bytes::from(pubkey));
+    payload.extend_from_array(&nonce.to_be_bytes());
+    payload.extend_from_array(&expiracion.to_be_bytes());
+    payload.append(&nombre.to_bytes());
+    payload
+}
+
+// Dirección de cuenta Stellar (G...) que corresponde a una clave ed25519
+// Armamos el XDR de ScVal::Address(ScAddress::Account(PublicKey::Ed25519)):
+//   [SCV_ADDRESS = 18][SC_ADDRESS_TYPE_ACCOUNT = 0][KEY_TYPE_ED25519 = 0][clave]
+fn direccion_de_clave(env: &Env, pubkey: &BytesN<32>) -> Address {
+    let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.append(&Bytes::from(pubkey));
+    // Siempre es XDR válido: cualquier clave de 32 bytes forma una cuenta
+    Address::from_xdr(env, &xdr).unwrap()
+}
+
+// Challenge que el front-end pasa a navigator.credentials.get()
+// Mismo esquema de dominio que hello_firmado, con su propio prefijo,
+// reducido con sha256 a los 32 bytes que viajan dentro de clientDataJSON
+fn challenge_passkey(env: &Env, nombre: &String, nonce: u64) -> BytesN<32> {
+    let mut challenge = Bytes::from_slice(env, DOMINIO_HELLO_PASSKEY);
+    challenge.append(&Bytes::from(env.ledger().network_id()));
+    challenge.append(&env.current_contract_address().to_xdr(env));
+    challenge.extend_from_array(&nonce.to_be_bytes());
+    challenge.append(&nombre.to_bytes());
+    env.crypto().sha256(&challenge).into()
+}
+
+// base64url sin padding, como lo escribe el navegador en clientDataJSON
+// 32 bytes = 10 grupos de 3 (40 caracteres) + 2 bytes sueltos (3 caracteres)
+fn base64url_32(datos: &[u8; 32]) -> [u8; 43] {
+    let mut salida = [0u8; 43];
+    for (i, grupo) in datos.chunks(3).enumerate() {
+        let b0 = grupo[0] as usize;
+        let b1 = grupo.get(1).copied().unwrap_or(0) as usize;
+        let b2 = grupo.get(2).copied().unwrap_or(0) as usize;
+        let indices = [b0 >> 2, ((b0 & 0x03) << 4) | (b1 >> 4), ((b1 & 0x0f) << 2) | (b2 >> 6), b2 & 0x3f];
+        for (j, indice) in indices.iter().take(grupo.len() + 1).enumerate() {
+            salida[i * 4 + j] = BASE64URL[*indice];
+        }
+    }
+    salida
+}
+
+fn contiene(texto: &[u8], buscado: &[u8]) -> bool {
+    texto.windows(buscado.len()).any(|ventana| ventana == buscado)
+}
+
+// Verifica los datos WebAuthn y retorna el mensaje que firmó el autenticador:
+// authenticatorData || sha256(clientDataJSON)
+// No hay parser JSON en no_std: los campos se buscan tal como los serializa
+// el navegador ("clave":"valor", sin espacios)
+fn mensaje_webauthn(
+    env: &Env,
+    challenge: &BytesN<32>,
+    authenticator_data: &Bytes,
+    client_data_json: &Bytes
+) -> Result<Bytes, ErrorExtra> {
+    // authenticatorData: la bandera UP confirma que hubo un gesto del usuario
+    if authenticator_data.len() < MIN_DATOS_AUTENTICADOR
+        || authenticator_data.get_unchecked(32) & BANDERA_USUARIO_PRESENTE == 0
+    {
+        return Err(ErrorExtra::DatosWebAuthnInvalidos);
+    }
+
+    let largo = client_data_json.len();
+    if largo > MAX_CLIENT_DATA_JSON {
+        return Err(ErrorExtra::DatosWebAuthnInvalidos);
+    }
+    let mut buffer = [0u8; MAX_CLIENT_DATA_JSON as usize];
+    let json = &mut buffer[..largo as usize];
+    client_data_json.copy_into_slice(json);
+
+    // "challenge":"<base64url>" con el challenge exacto de este nombre y nonce
+    let mut campo = [0u8; 57];
+    campo[..13].copy_from_slice(b"\"challenge\":\"");
+    campo[13..56].copy_from_slice(&base64url_32(&challenge.to_array()));
+    campo[56] = b'"';
+
+    if !contiene(json, b"\"type\":\"webauthn.get\"") || !contiene(json, &campo) {
+        return Err(ErrorExtra::DatosWebAuthnInvalidos);
+    }
+
+    let mut mensaje = authenticator_data.clone();
+    mensaje.append(&Bytes::from(env.crypto().sha256(client_data_json)));
+    Ok(mensaje)
+}
+
+// ============================================================================
+// HELPERS DEL CONSEJO
+// ============================================================================
+
+fn leer_consejo(env: &Env) -> Option<Consejo> {
+    env.storage().instance().get(&DataKey::Consejo)
+}
+
+// Las acciones que maneja el consejo no se pueden llamar directo si existe
+fn verificar_sin_consejo(env: &Env) -> Result<(), Error> {
+    if env.storage().instance().has(&DataKey::Consejo) {
+        return Err(Error::RequiereConsejo);
+    }
+    Ok(())
+}
+
+fn verificar_firmante(env: &Env, firmante: &Address) -> Result<Consejo, Error> {
+    let consejo = leer_consejo(env).ok_or(Error::NoInicializado)?;
+    if !consejo.firmantes.contains(firmante) {
+        return Err(Error::NoAutorizado);
+    }
+    Ok(consejo)
+}
+
+fn validar_consejo(consejo: &Consejo) -> Result<(), Error> {
+    let n = consejo.firmantes.len();
+    if n == 0 || n > MAX_FIRMANTES || consejo.umbral == 0 || consejo.umbral > n {
+        return Err(Error::ConsejoInvalido);
+    }
+
+    // Sin firmantes repetidos: cada uno cuenta una sola vez para el umbral
+    for (i, firmante) in consejo.firmantes.iter().enumerate() {
+        if consejo.firmantes.first_index_of(&firmante) != Some(i as u32) {
+            return Err(Error::ConsejoInvalido);
+        }
+    }
+
+    Ok(())
+}
+
+fn validar_accion(accion: &AccionConsejo) -> Result<(), Error> {
+    match accion {
+        AccionConsejo::ResetContador => Ok(()),
+        AccionConsejo::SetLimiteNombre(limite) => validar_limite(*limite),
+        AccionConsejo::CambiarConsejo(consejo) => validar_consejo(consejo),
+        AccionConsejo::CancelarCambio(_) => Ok(()),
+    }
+}
+
+fn leer_pendientes(env: &Env) -> Vec<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PropuestasPendientes)
+        .unwrap_or(Vec::new(env))
+}
+
+fn leer_propuesta(env: &Env, id: u32) -> Result<Propuesta, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Propuesta(id))
+        .ok_or(Error::PropuestaNoExiste)
+}
+
+fn guardar_propuesta(env: &Env, propuesta: &Propuesta) {
+    let key = DataKey::Propuesta(propuesta.id);
+    env.storage().persistent().set(&key, propuesta);
+    env.storage().persistent().extend_ttl(&key, 100, 100);
+}
+
+// Ejecuta la acción aprobada y cierra la propuesta
+// `firmante` es quien completó el umbral: la auditoría queda a su nombre
+fn ejecutar_propuesta(env: &Env, firmante: &Address, mut propuesta: Propuesta) -> Result<(), Error> {
+    let mut pendientes = leer_pendientes(env);
+
+    match &propuesta.accion {
+        AccionConsejo::ResetContador => {
+            let anterior = leer_contador(env, &SALA_GENERAL);
+            guardar_contador(env, &SALA_GENERAL, 0);
+            registrar_auditoria(
+                env,
+                firmante.clone(),
+                AccionAuditoria::ResetContadorConsejo,
+                None,
+                ValorAuditoria::Numero(anterior),
+                ValorAuditoria::Numero(0),
+                None
+            );
+        }
+        AccionConsejo::SetLimiteNombre(limite) => aplicar_limite_decidido(
+            env,
+            firmante.clone(),
+            AccionAuditoria::SetLimiteConsejo,
+            OrigenCambio::Consejo,
+            *limite
+        )?,
+        AccionConsejo::CancelarCambio(id) => {
+            cancelar(env, *id)?;
+        }
+        AccionConsejo::CambiarConsejo(consejo) => {
+            let anterior = leer_consejo(env);
+            env.storage().instance().set(&DataKey::Consejo, consejo);
+            registrar_auditoria(
+                env,
+                firmante.clone(),
+                AccionAuditoria::CambiarConsejo,
+                None,
+                anterior.map_or(ValorAuditoria::Ninguno, ValorAuditoria::Consejo),
+                ValorAuditoria::Consejo(consejo.clone()),
+                None
+            );
+
+            // Las aprobaciones del consejo anterior ya no valen:
+            // cancelamos todo lo que seguía pendiente
+            for id in pendientes.iter() {
+                if id != propuesta.id {
+                    if let Ok(mut otra) = leer_propuesta(env, id) {
+                        otra.estado = EstadoPropuesta::Cancelada;
+                        guardar_propuesta(env, &otra);
+                    }
+                }
+            }
+            pendientes = Vec::new(env);
+        }
+    }
+
+    if let Some(i) = pendientes.first_index_of(propuesta.id) {
+        pendientes.remove(i);
+    }
+    env.storage().instance().set(&DataKey::PropuestasPendientes, &pendientes);
+    env.storage().instance().extend_ttl(100, 100);
+
+    propuesta.estado = EstadoPropuesta::Ejecutada;
+    guardar_propuesta(env, &propuesta);
+
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DEL TIMELOCK
+// ============================================================================
+
+fn leer_retraso(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RetrasoCambios)
+        .unwrap_or(0)
+}
+
+fn leer_cambios(env: &Env) -> Vec<CambioProgramado> {
+    env.storage()
+        .instance()
+        .get(&DataKey::CambiosProgramados)
+        .unwrap_or(Vec::new(env))
+}
+
+fn programar_cambio(env: &Env, caller: &Address, cambio: CambioConfig) -> Result<u32, Error> {
+    verificar_admin(env, &SALA_GENERAL, caller)?;
+    verificar_sin_consejo(env)?;
+    encolar_cambio(env, cambio, OrigenCambio::Admin)
+}
+
+// Pone el cambio en cola sin revisar permisos: quien llama ya decidió
+// (el admin en programar_cambio, o el consejo en ejecutar_propuesta)
+fn encolar_cambio(env: &Env, cambio: CambioConfig, origen: OrigenCambio) -> Result<u32, Error> {
+    let mut cambios = leer_cambios(env);
+    if cambios.len() >= MAX_CAMBIOS {
+        return Err(Error::DemasiadosCambios);
+    }
+
+    let id: u32 = env.storage()
+        .instance()
+        .get(&DataKey::ProximoCambio)
+        .unwrap_or(1);
+
+    let programado = CambioProgramado {
+        id,
+        cambio,
+        ejecutable_desde: env.ledger().sequence().saturating_add(leer_retraso(env)),
+        origen,
+    };
+
+    cambios.push_back(programado.clone());
+    env.storage().instance().set(&DataKey::CambiosProgramados, &cambios);
+    env.storage().instance().set(&DataKey::ProximoCambio, &(id + 1));
+    env.storage().instance().extend_ttl(100, 100);
+
+    CambioEnCola {
+        id,
+        cambio: programado.cambio,
+        ejecutable_desde: programado.ejecutable_desde,
+    }
+    .publish(env);
+
+    Ok(id)
+}
+
+// Límite ya aprobado por el consejo o por una votación: con timelock
+// activo espera en la cola como cualquier otro cambio, si no se aplica ya.
+// En la cola la entrada de auditoría la deja ejecutar_cambio()
+fn aplicar_limite_decidido(
+    env: &Env,
+    actor: Address,
+    accion: AccionAuditoria,
+    origen: OrigenCambio,
+    limite: u32
+) -> Result<(), Error> {
+    if leer_retraso(env) > 0 {
+        encolar_cambio(env, CambioConfig::LimiteNombre(limite), origen)?;
+    } else {
+        let anterior = leer_limite(env, &SALA_GENERAL);
+        guardar_limite(env, &SALA_GENERAL, limite);
+        registrar_auditoria(
+            env,
+            actor,
+            accion,
+            None,
+            ValorAuditoria::Numero(anterior as u64),
+            ValorAuditoria::Numero(limite as u64),
+            None
+        );
+    }
+    Ok(())
+}
+
+// Saca el cambio `id` de la cola y lo retorna
+fn sacar_cambio(env: &Env, id: u32) -> Result<CambioProgramado, Error> {
+    let mut cambios = leer_cambios(env);
+    let i = cambios
+        .iter()
+        .position(|c| c.id == id)
+        .ok_or(Error::CambioNoExiste)?;
+
+    let cambio = cambios.get_unchecked(i as u32);
+    cambios.remove(i as u32);
+    env.storage().instance().set(&DataKey::CambiosProgramados, &cambios);
+
+    Ok(cambio)
+}
+
+// Cancelación desde cancelar_cambio() o desde una propuesta del consejo:
+// lo que ganó una votación no lo deshace ni el admin ni el consejo
+fn cancelar(env: &Env, id: u32) -> Result<(), Error> {
+    let cambio = leer_cambios(env)
+        .iter()
+        .find(|c| c.id == id)
+        .ok_or(Error::CambioNoExiste)?;
+    if cambio.origen == OrigenCambio::Votacion {
+        return Err(Error::NoAutorizado);
+    }
+    sacar_cambio(env, id)?;
+    Ok(())
+}
+
+// ============================================================================
+// HELPERS DE GOBERNANZA
+// ============================================================================
+// Para conocer el contador de cada usuario en el ledger de una votación sin
+// recorrer a todos los usuarios, guardamos checkpoints (ledger, valor).
+// Solo se guardan después de la primera votación: antes nadie los necesita.
+// ============================================================================
+
+fn leer_gobernanza(env: &Env) -> ConfigGobernanza {
+    env.storage()
+        .instance()
+        .get(&DataKey::ConfigGobernanza)
+        .unwrap_or(ConfigGobernanza {
+            quorum: QUORUM_POR_DEFECTO,
+            periodo: PERIODO_POR_DEFECTO,
+        })
+}
+
+// Llamado por saludar() cuando cambia el contador de la sala general
+fn registrar_checkpoint(env: &Env, usuario: &Address, anterior: u64, nuevo: u64) {
+    let ultima_votacion: u32 = match env.storage().instance().get(&DataKey::UltimaVotacion) {
+        Some(ledger) => ledger,
+        None => return,  // Sin votaciones no hace falta historial
+    };
+
+    let key = DataKey::Checkpoints(usuario.clone());
+    let ahora = env.ledger().sequence();
+    let mut puntos: Vec<Checkpoint> = env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| {
+            // Primer checkpoint: el valor anterior vale "desde siempre",
+            // porque los saludos sin registrar son previos a toda votación
+            let mut puntos = Vec::new(env);
+            if anterior > 0 {
+                puntos.push_back(Checkpoint { ledger: 0, valor: anterior });
+            }
+            puntos
+        });
+
+    // Se puede pisar el último checkpoint si ninguna votación lo usa como
+    // instantánea (ninguna empezó después de ese ledger)
+    match puntos.last() {
+        Some(ultimo) if ultimo.ledger == ahora || ultima_votacion <= ultimo.ledger => {
+            puntos.set(puntos.len() - 1, Checkpoint { ledger: ultimo.ledger, valor: nuevo });
+        }
+        _ => puntos.push_back(Checkpoint { ledger: ahora, valor: nuevo }),
+    }
+
+    env.storage().persistent().set(&key, &puntos);
+    env.storage().persistent().extend_ttl(&key, 100, 100);
+}
+
+// Contador del usuario antes del ledger `inicio`
+fn peso_de_voto(env: &Env, usuario: &Address, inicio: u32) -> u64 {
+    let puntos: Option<Vec<Checkpoint>> = env.storage()
+        .persistent()
+        .get(&DataKey::Checkpoints(usuario.clone()));
+
+    match puntos {
+        // Sin checkpoints no saludó desde la primera votación:
+        // su contador actual es el mismo que tenía en `inicio`
+        None => leer_contador_usuario(env, &SALA_GENERAL, usuario),
+        Some(puntos) => puntos
+            .iter()
+            .filter(|p| p.ledger < inicio)
+            .last()
+            .map(|p| p.valor)
+            .unwrap_or(0),
+    }
+}
+
+fn leer_votacion(env: &Env, id: u32) -> Result<Votacion, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Votacion(id))
+        .ok_or(Error::VotacionNoExiste)
+}
+
+fn guardar_votacion(env: &Env, votacion: &Votacion) {
+    let key = DataKey::Votacion(votacion.id);
+    env.storage().persistent().set(&key, votacion);
+    env.storage().persistent().extend_ttl(&key, 100, 100);
+}
+
+// ============================================================================
+// HELPERS DE ACCIONES DE MODERACIÓN
+// ============================================================================
+
+// Lectura del último saludo respetando la moderación:
+// un saludo oculto se reemplaza por SALUDO_OCULTO
+fn leer_ultimo_saludo(env: &Env, sala: &Symbol, usuario: Address) -> Option<String> {
+    let oculto = es_general(sala)
+        && env.storage().persistent().has(&DataKey::SaludoOculto(usuario.clone()));
+
+    let saludo: Option<String> = env.storage()
+        .persistent()
+        .get(&key_ultimo_saludo(sala, usuario));
+
+    match saludo {
+        Some(_) if oculto => Some(String::from_str(env, SALUDO_OCULTO)),
+        saludo => saludo,
+    }
+}
+
+fn leer_moderacion(env: &Env, id: u32) -> Result<AccionModeracion, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Moderacion(id))
+        .ok_or(Error::AccionNoExiste)
+}
+
+// ============================================================================
+// HELPERS DE AUDITORÍA
+// ============================================================================
+
+fn validar_motivo(motivo: &String) -> Result<(), ErrorExtra> {
+    if motivo.is_empty() || motivo.len() > MAX_LARGO_MOTIVO {
+        return Err(ErrorExtra::MotivoInvalido);
+    }
+    Ok(())
+}
+
+fn leer_retencion_auditoria(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::RetencionAuditoria)
+        .unwrap_or(RETENCION_AUDITORIA_POR_DEFECTO)
+}
+
+fn leer_proxima_auditoria(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::ProximaAuditoria)
+        .unwrap_or(1)
+}
+
+// Primer id que sigue guardado (puede estar vencido hasta la próxima poda)
+fn leer_primera_auditoria(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExtra::PrimeraAuditoria)
+        .unwrap_or(1)
+}
+
+fn auditoria_vigente(env: &Env, entrada: &EntradaAuditoria) -> bool {
+    entrada.ledger.saturating_add(leer_retencion_auditoria(env)) > env.ledger().sequence()
+}
+
+// Lo que quedó fuera de la retención ya no se muestra aunque la poda
+// todavía no lo haya borrado
+fn leer_auditoria(env: &Env, id: u32) -> Option<EntradaAuditoria> {
+    if id < leer_primera_auditoria(env) {
+        return None;
+    }
+    let entrada: EntradaAuditoria = env.storage()
+        .persistent()
+        .get(&DataKeyExtra::Auditoria(id))?;
+    auditoria_vigente(env, &entrada).then_some(entrada)
+}
+
+// Borra hasta MAX_PODA_AUDITORIA entradas vencidas, de la más vieja en
+// adelante. Acotado para que una racha de entradas vencidas (un contrato
+// que estuvo quieto) no haga cara una sola llamada: el resto se poda en
+// las próximas entradas
+fn podar_auditoria(env: &Env) {
+    let mut primera = leer_primera_auditoria(env);
+    let proxima = leer_proxima_auditoria(env);
+
+    let mut podadas = 0;
+    while primera < proxima && podadas < MAX_PODA_AUDITORIA {
+        let key = DataKeyExtra::Auditoria(primera);
+        let entrada: Option<EntradaAuditoria> = env.storage().persistent().get(&key);
+        if entrada.is_some_and(|e| auditoria_vigente(env, &e)) {
+            break;
+        }
+        env.storage().persistent().remove(&key);
+        primera += 1;
+        podadas += 1;
+    }
+
+    env.storage().instance().set(&DataKeyExtra::PrimeraAuditoria, &primera);
+}
+
+// Guarda la entrada, emite Auditado y retorna el id
+fn registrar_auditoria(
+    env: &Env,
+    actor: Address,
+    accion: AccionAuditoria,
+    usuario: Option<Address>,
+    anterior: ValorAuditoria,
+    nuevo: ValorAuditoria,
+    motivo: Option<String>
+) -> u32 {
+    guardar_auditoria(env, EntradaAuditoria {
+        id: tomar_id_auditoria(env),
+        actor,
+        accion,
+        usuario,
+        sala: None,
+        anterior,
+        nuevo,
+        motivo,
+        ledger: env.ledger().sequence(),
+    })
+}
+
+// Lo mismo para lo que hace el admin de una sala creada
+fn registrar_auditoria_sala(
+    env: &Env,
+    sala: &Symbol,
+    actor: Address,
+    accion: AccionAuditoria,
+    anterior: ValorAuditoria,
+    nuevo: ValorAuditoria
+) -> u32 {
+    guardar_auditoria(env, EntradaAuditoria {
+        id: tomar_id_auditoria(env),
+        actor,
+        accion,
+        usuario: None,
+        sala: Some(sala.clone()),
+        anterior,
+        nuevo,
+        motivo: None,
+        ledger: env.ledger().sequence(),
+    })
+}
+
+fn tomar_id_auditoria(env: &Env) -> u32 {
+    let id = leer_proxima_auditoria(env);
+    env.storage().instance().set(&DataKeyExtra::ProximaAuditoria, &(id + 1));
+    id
+}
+
+fn guardar_auditoria(env: &Env, entrada: EntradaAuditoria) -> u32 {
+    let key = DataKeyExtra::Auditoria(entrada.id);
+    env.storage().persistent().set(&key, &entrada);
+    env.storage().persistent().extend_ttl(&key, TTL_AUDITORIA, TTL_AUDITORIA);
+    podar_auditoria(env);
+
+    let EntradaAuditoria { id, actor, accion, usuario, sala, anterior, nuevo, .. } = entrada;
+    Auditado { accion, id, actor, usuario, sala, anterior, nuevo }.publish(env);
+
+    id
+}
+
+fn guardar_moderacion(env: &Env, accion: &AccionModeracion) {
+    let key = DataKey::Moderacion(accion.id);
+    env.storage().persistent().set(&key, accion);
+    env.storage().persistent().extend_ttl(&key, 100, 100);
+}
+
+// Crea la acción, la agrega al log del usuario y retorna su id
+fn registrar_moderacion(
+    env: &Env,
+    moderador: Address,
+    usuario: Address,
+    tipo: TipoModeracion,
+    motivo: MotivoModeracion,
+    hasta: u32
+) -> u32 {
+    let id: u32 = env.storage()
+        .instance()
+        .get(&DataKey::ProximaModeracion)
+        .unwrap_or(1);
+    env.storage().instance().set(&DataKey::ProximaModeracion, &(id + 1));
+
+    let key_log = DataKey::LogModeracion(usuario.clone());
+    let mut log: Vec<u32> = env.storage()
+        .persistent()
+        .get(&key_log)
+        .unwrap_or(Vec::new(env));
+    log.push_back(id);
+    env.storage().persistent().set(&key_log, &log);
+    env.storage().persistent().extend_ttl(&key_log, 100, 100);
+
+    guardar_moderacion(env, &AccionModeracion {
+        id,
+        usuario,
+        moderador,
+        tipo,
+        motivo,
+        ledger: env.ledger().sequence(),
+        hasta,
+        apelacion: EstadoApelacion::SinApelar,
+        mensaje_apelacion: None,
+    });
+
+    id
+}
+
+// Validaciones de todas las variantes de hello (no escriben estado)
+fn validar_saludo(
+    env: &Env,
+    sala: &Symbol,
+    usuario: &Address,
+    nombre: &String,
+    membresia_probada: bool
+) -> Result<(), Error> {
+
+    // VALIDACIÓN 0: Sin initialize (o constructor) no se escribe nada
+    verificar_inicializado(env)?;
+
+    // VALIDACIÓN 1: Nombre no puede estar vacío (no requiere storage)
+    if nombre.is_empty() {
+        return Err(Error::NombreVacio);
+    }
+
+    // VALIDACIÓN 2: Las salas con nombre tienen que existir
+    if !es_general(sala) && leer_admin(env, sala).is_none() {
+        return Err(Error::SalaNoExiste);
+    }
+
+    // VALIDACIÓN 3: Nombre dentro del límite configurado para la sala
+    if nombre.len() > leer_limite(env, sala) {
+        return Err(Error::NombreMuyLargo);
+    }
+
+    // VALIDACIÓN 4: Nombre sin palabras prohibidas (ya sabemos que es corto)
+    verificar_palabras(env, nombre)?;
+
+    // VALIDACIÓN 5: Si se exige, el nombre es el que el usuario registró
+    verificar_nombre_registrado(env, usuario, nombre)?;
+
+    // VALIDACIÓN 6: El usuario puede saludar (no bloqueado, en la lista)
+    verificar_acceso(env, usuario, membresia_probada)
+}
+
+// PASOS 2 y 3 de saludar(): último saludo y contador del usuario
+// hello_lote() los usa por entrada y guarda el contador de la sala una vez
+fn registrar_saludo_usuario(
+    env: &Env,
+    sala: &Symbol,
+    usuario: &Address,
+    nombre: &String
+) -> Result<(), Error> {
+    // Primero lo que puede fallar, para no dejar escrituras a medias
+    let contador_usuario = leer_contador_usuario(env, sala, usuario);
+    let nuevo = incrementar(env, contador_usuario)?;
+
+    // Guardar el último saludo del usuario en esta sala
+    // Un saludo nuevo reemplaza al que estaba oculto por moderación
+    let key_saludo = key_ultimo_saludo(sala, usuario.clone());
+    env.storage().persistent().set(&key_saludo, nombre);
+    env.storage().persistent().extend_ttl(&key_saludo, 100, 100);
+    if es_general(sala) {
+        env.storage().persistent().remove(&DataKey::SaludoOculto(usuario.clone()));
+    }
+
+    // Incrementar el contador individual del usuario en esta sala
+    let key_usuario = key_contador_usuario(sala, usuario.clone());
+    env.storage()
+        .persistent()
+        .set(&key_usuario, &nuevo);
+
+    env.storage()
+        .persistent()
+        .extend_ttl(&key_usuario, 100, 100);
+
+    // El contador de la sala general es el peso de voto en la gobernanza
+    if es_general(sala) {
+        registrar_checkpoint(env, usuario, contador_usuario, nuevo);
+    }
+
+    Ok(())
+}
+
+// Lógica compartida por hello() y hello_en_sala()
+fn saludar(
+    env: &Env,
+    sala: &Symbol,
+    usuario: Address,
+    nombre: String,
+    membresia_probada: bool
+) -> Result<String, Error> {
+    validar_saludo(env, sala, &usuario, &nombre, membresia_probada)?;
+
+    // PASO 1: Incrementar contador de la sala (Leer → Modificar → Guardar)
+    // incrementar() no hace panic en u64::MAX: falla o satura según el modo
+    let contador = incrementar(env, leer_contador(env, sala))?;
+    guardar_contador(env, sala, contador);
+
+    // PASOS 2 y 3: Último saludo y contador individual del usuario
+    registrar_saludo_usuario(env, sala, &usuario, &nombre)?;
+
+    // PASO 4: Mantener vivo el instance storage
+    env.storage()
+        .instance()
+        .extend_ttl(100, 100);
+
+    // PASO 5: Avisar a los hooks con el estado ya guardado
+    // Si un hook obligatorio falla, el Err revierte todo lo anterior
+    ejecutar_hooks(env, &usuario, &nombre, contador)?;
+
+    // PASO 6: Responder en el idioma del usuario (String: Symbol no admite acentos)
+    Ok(saludo_para(env, &usuario))
+}
+
+// ============================================================================
+// IMPLEMENTACIÓN DEL CONTRATO HELLO TIBURONA
+// ============================================================================
+// Este bloque implementa todas las funciones públicas del contrato.
+// El macro #[contractimpl] convierte estas funciones en endpoints del contrato
+// que pueden ser llamados desde fuera de la blockchain.
+
+#[contractimpl]
+impl HelloContract {
+
+    // ========================================================================
+    // FUNCIÓN: __constructor
+    // ========================================================================
+    // Propósito: Inicializar el contrato en el mismo despliegue
+    // Parámetros:
+    //   - admin: Some(admin) = queda inicializado al desplegar
+    //            (env.register(HelloContract, (admin,)) en los tests)
+    //            None = despliegue en dos pasos, después se llama initialize()
+    // El host lo ejecuta una sola vez, al crear el contrato: nadie puede
+    // ganarle de mano a initialize() entre el despliegue y la configuración
+    // ========================================================================
+    pub fn __constructor(env: Env, admin: Option<Address>) -> Result<(), Error> {
+        match admin {
+            Some(admin) => Self::initialize(env, admin),
+            None => Ok(()),
+        }
+    }
+
+    // ========================================================================
+    // FUNCIÓN: initialize
+    // ========================================================================
+    // Propósito: Configurar el contrato por primera vez
+    // Parámetros:
+    //   - env: Ambiente de Soroban (acceso a storage, eventos, etc.)
+    //   - admin: Dirección que será el administrador del contrato
+    // Retorna: Result<(), Error> - Ok si exitoso, Err si falla
+    // ========================================================================
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+
+        // PASO 1: Verificar que el contrato NO esté ya inicializado
+        // --------------------------------------------------------
+        // .has() verifica si existe una key en storage sin deserializar el valor
+        // Es más eficiente que .get() porque solo chequea existencia
+        if env.storage().instance().has(&DataKey::Admin) {
+            // Si ya hay un admin guardado, el contrato ya fue inicializado
+            // Retornamos error #49 (YaInicializado) para evitar re-inicialización
+            // (antes era #4: ver Error::codigo_v1 para clientes viejos)
+            return Err(Error::YaInicializado);
+        }
+
+        // PASO 2: Guardar el administrador en Instance Storage
+        // -----------------------------------------------------
+        // Instance storage = datos globales del contrato
+        // .set() toma dos parámetros: key y valor (ambos por referencia &)
+        env.storage()
+            .instance()                          // Acceder a instance storage
+            .set(&DataKey::Admin, &admin);       // Guardar admin con key "Admin"
+
+        // PASO 3: Inicializar contador de saludos en 0
+        // ---------------------------------------------
+        // &0u64 = referencia a un unsigned 64-bit integer con valor 0
+        // Inicializamos explícitamente en 0 para documentar el estado inicial
+        // Un contrato nuevo ya nace con el esquema actual (contadores u64)
+        env.storage()
+            .instance()
+            .set(&DataKey::ContadorSaludos, &0u64);
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExtra::VersionEsquema, &VERSION_ESQUEMA);
+
+        // PASO 4: Inicializar el límite de caracteres por defecto (32)
+        // -------------------------------------------------------------
+        // El admin puede cambiarlo después con set_limite_nombre()
+        env.storage()
+            .instance()
+            .set(&DataKey::LimiteNombre, &LIMITE_POR_DEFECTO);
+
+        // PASO 5: Extender el TTL (Time To Live) del instance storage
+        // ------------------------------------------------------------
+        // TTL = cuánto tiempo persisten los datos en blockchain
+        // extend_ttl(100, 100) significa:
+        //   - Si quedan menos de 100 ledgers de vida (threshold)
+        //   - Extiende por 100 ledgers más (extend_to)
+        // Esto asegura que la configuración no expire accidentalmente
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        // PASO 6: Retornar éxito
+        // -----------------------
+        // Ok(()) = operación exitosa sin valor de retorno
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello
+    // ========================================================================
+    // Propósito: Saludar a una Tiburona y registrar el evento
+    // Parámetros:
+    //   - env: Ambiente de Soroban
+    //   - usuario: Dirección de quien saluda
+    //   - nombre: Nombre de la Tiburona (String para validar longitud)
+    // Retorna: Result<String, Error> - Saludo en el idioma del usuario
+    //          ("Hola" por defecto) si exitoso, Error si falla
+    // ========================================================================
+    pub fn hello(
+        env: Env,
+        usuario: Address,
+        nombre: String
+    ) -> Result<String, Error> {
+
+        // El saludo queda a nombre de `usuario`: tiene que firmarlo él.
+        // Sin esto la lista permitida, los bloqueos y las suspensiones
+        // solo revisarían una dirección elegida por quien llama
+        usuario.require_auth();
+
+        // hello() es el saludo "legacy": siempre va a la sala general
+        // Las validaciones y el patrón Leer → Modificar → Guardar viven en
+        // saludar(), compartido con hello_en_sala()
+        saludar(&env, &SALA_GENERAL, usuario, nombre, false)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_contador
+    // ========================================================================
+    // Propósito: Consultar el contador total de saludos
+    // Retorna: Result<u64, Error> - Número total de saludos
+    //   - NoInicializado: falta initialize() (o el constructor)
+    // ========================================================================
+    pub fn get_contador(env: Env) -> Result<u64, Error> {
+        // Esta es una función de SOLO LECTURA
+        // No modifica estado → más barata de ejecutar
+        // Retorna Result solo por la guardia: sin admin no hay contador que leer
+        verificar_inicializado(&env)?;
+
+        Ok(leer_contador(&env, &SALA_GENERAL))
+
+        // Si no existe el contador se retorna 0:
+        // - No existe = 0 es un valor válido (nadie ha saludado)
+        // - No hay posibilidad de panic
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_ultimo_saludo
+    // ========================================================================
+    // Propósito: Consultar el último saludo de un usuario específico
+    // Parámetros:
+    //   - env: Ambiente de Soroban
+    //   - usuario: Dirección del usuario a consultar
+    // Retorna: Result<Option<String>, Error> - Some(nombre) si existe, None si nunca saludó
+    // ========================================================================
+    pub fn get_ultimo_saludo(env: Env, usuario: Address) -> Result<Option<String>, Error> {
+        verificar_inicializado(&env)?;
+
+        // Función de SOLO LECTURA
+        // Retorna Option<String> porque el usuario puede no haber saludado nunca
+
+        Ok(leer_ultimo_saludo(&env, &SALA_GENERAL, usuario))
+
+        // Retorna Option<String>:
+        // - Some(nombre) = el usuario ha saludado
+        // - Some("[oculto]") = un moderador ocultó el saludo (ver saludo_oculto)
+        // - None = el usuario nunca ha saludado (o el saludo fue borrado)
+
+        // NO usamos unwrap_or() porque queremos distinguir:
+        //   - "No ha saludado" (None) vs "Saludó con texto vacío" (Some(""))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_contador_usuario
+    // ========================================================================
+    // Propósito: Consultar cuántas veces saludó un usuario en la sala general
+    // Retorna: Result<u64, Error> - 0 si el usuario nunca saludó
+    // ========================================================================
+    pub fn get_contador_usuario(env: Env, usuario: Address) -> Result<u64, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_contador_usuario(&env, &SALA_GENERAL, &usuario))
+    }
+
+    // ========================================================================
+    // FUNCIONES: get_saludos_multiples / get_contadores_multiples
+    // ========================================================================
+    // Propósito: Lo mismo que get_ultimo_saludo / get_contador_usuario, pero
+    //            para muchas direcciones en una sola simulación (dashboards)
+    // Parámetros:
+    //   - usuarios: Hasta MAX_CONSULTA direcciones
+    // Retorna: Result<Vec<_>, soroban_sdk::Error> - el resultado i es de usuarios[i]
+    // ========================================================================
+    pub fn get_saludos_multiples(
+        env: Env,
+        usuarios: Vec<Address>
+    ) -> Result<Vec<Option<String>>, soroban_sdk::Error> {
+        verificar_inicializado(&env)?;
+
+        if usuarios.len() > MAX_CONSULTA {
+            return Err(ErrorExtra::ConsultaMuyGrande.into());
+        }
+
+        let mut saludos = Vec::new(&env);
+        for usuario in usuarios.iter() {
+            saludos.push_back(leer_ultimo_saludo(&env, &SALA_GENERAL, usuario));
+        }
+        Ok(saludos)
+    }
+
+    pub fn get_contadores_multiples(env: Env, usuarios: Vec<Address>) -> Result<Vec<u64>, soroban_sdk::Error> {
+        verificar_inicializado(&env)?;
+
+        if usuarios.len() > MAX_CONSULTA {
+            return Err(ErrorExtra::ConsultaMuyGrande.into());
+        }
+
+        let mut contadores = Vec::new(&env);
+        for usuario in usuarios.iter() {
+            contadores.push_back(leer_contador_usuario(&env, &SALA_GENERAL, &usuario));
+        }
+        Ok(contadores)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: reset_contador
+    // ========================================================================
+    // Propósito: Resetear el contador a 0 (SOLO ADMIN)
+    // Parámetros:
+    //   - env: Ambiente de Soroban
+    //   - caller: Dirección de quien llama esta función
+    // Retorna: Result<(), Error> - Ok si exitoso, Error si no autorizado
+    // ========================================================================
+    pub fn reset_contador(env: Env, caller: Address) -> Result<(), Error> {
+
+        // PASO 1: Verificar que caller sea el admin
+        // ------------------------------------------
+        // verificar_admin() lee el admin guardado y lo compara con caller:
+        //   - Sin admin → Error #4 (NoInicializado)
+        //   - caller != admin → Error #3 (NoAutorizado)
+        // El ? propaga el error automáticamente
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        // PASO 2: Con consejo configurado hay que usar proponer()
+        // --------------------------------------------------------
+        verificar_sin_consejo(&env)?;
+
+        // PASO 3: Resetear el contador a 0
+        // ---------------------------------
+        // Si llegamos aquí, caller ES el admin
+        // Seguro modificar el estado
+        let anterior = leer_contador(&env, &SALA_GENERAL);
+        guardar_contador(&env, &SALA_GENERAL, 0);
+
+        // PASO 4: Dejar el rastro en la auditoría
+        // ----------------------------------------
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::ResetContador,
+            None,
+            ValorAuditoria::Numero(anterior),
+            ValorAuditoria::Numero(0),
+            None
+        );
+
+        // PASO 5: Retornar éxito
+        // -----------------------
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_contador
+    // ========================================================================
+    // Propósito: Corregir el contador global a un valor exacto (SOLO ADMIN)
+    //            sin tener que resetearlo a 0
+    // Parámetros:
+    //   - motivo: Por qué se corrige (1..=256 caracteres), queda auditado
+    // Retorna: Result<u32, soroban_sdk::Error> - id de la entrada de auditoría
+    // ========================================================================
+    pub fn set_contador(
+        env: Env,
+        caller: Address,
+        valor: u64,
+        motivo: String
+    ) -> Result<u32, soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        validar_motivo(&motivo)?;
+
+        let anterior = leer_contador(&env, &SALA_GENERAL);
+        guardar_contador(&env, &SALA_GENERAL, valor);
+
+        Ok(registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::SetContador,
+            None,
+            ValorAuditoria::Numero(anterior),
+            ValorAuditoria::Numero(valor),
+            Some(motivo)
+        ))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: ajustar_contador_usuario
+    // ========================================================================
+    // Propósito: Corregir el ContadorPorUsuario de un usuario (SOLO ADMIN)
+    // El contador también es peso de voto: el cambio queda en sus
+    // checkpoints igual que un saludo, así no altera votaciones ya abiertas
+    // Retorna: Result<u32, soroban_sdk::Error> - id de la entrada de auditoría
+    // ========================================================================
+    pub fn ajustar_contador_usuario(
+        env: Env,
+        caller: Address,
+        usuario: Address,
+        valor: u64,
+        motivo: String
+    ) -> Result<u32, soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        validar_motivo(&motivo)?;
+
+        let anterior = leer_contador_usuario(&env, &SALA_GENERAL, &usuario);
+        let key = DataKey::ContadorPorUsuario(usuario.clone());
+        env.storage().persistent().set(&key, &valor);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+        registrar_checkpoint(&env, &usuario, anterior, valor);
+
+        Ok(registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::AjustarContadorUsuario,
+            Some(usuario),
+            ValorAuditoria::Numero(anterior),
+            ValorAuditoria::Numero(valor),
+            Some(motivo)
+        ))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: borrar_ultimo_saludo
+    // ========================================================================
+    // Propósito: Borrar el UltimoSaludo de un usuario (SOLO ADMIN)
+    // A diferencia de borrar_saludo() de los moderadores, no crea una acción
+    // apelable: es una corrección del admin y queda en la auditoría
+    // Retorna: Result<u32, soroban_sdk::Error> - id de la entrada de auditoría
+    //   - SinSaludo: el usuario no tiene último saludo
+    // ========================================================================
+    pub fn borrar_ultimo_saludo(
+        env: Env,
+        caller: Address,
+        usuario: Address,
+        motivo: String
+    ) -> Result<u32, soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        validar_motivo(&motivo)?;
+
+        let key = DataKey::UltimoSaludo(usuario.clone());
+        let anterior: String = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::SinSaludo)?;
+
+        env.storage().persistent().remove(&key);
+        env.storage().persistent().remove(&DataKey::SaludoOculto(usuario.clone()));
+
+        Ok(registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::BorrarUltimoSaludo,
+            Some(usuario),
+            ValorAuditoria::Texto(anterior),
+            ValorAuditoria::Ninguno,
+            Some(motivo)
+        ))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_auditoria
+    // ========================================================================
+    // Retorna: Result<Option<EntradaAuditoria>, Error> - None si el id no existe o ya
+    //          quedó fuera de la retención
+    // ========================================================================
+    pub fn get_auditoria(env: Env, id: u32) -> Result<Option<EntradaAuditoria>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_auditoria(&env, id))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_auditorias
+    // ========================================================================
+    // Propósito: Leer el log de auditoría por páginas, del más viejo al
+    //            más nuevo
+    // Parámetros:
+    //   - desde: Primer id a leer (las entradas vencidas se saltean)
+    //   - limite: Máximo de entradas (1..=MAX_CONSULTA)
+    // Para la página siguiente: desde = id de la última entrada + 1
+    // ========================================================================
+    pub fn get_auditorias(
+        env: Env,
+        desde: u32,
+        limite: u32
+    ) -> Result<Vec<EntradaAuditoria>, soroban_sdk::Error> {
+        verificar_inicializado(&env)?;
+
+        if limite > MAX_CONSULTA {
+            return Err(ErrorExtra::ConsultaMuyGrande.into());
+        }
+
+        let mut entradas = Vec::new(&env);
+        let proxima = leer_proxima_auditoria(&env);
+        let mut id = desde.max(leer_primera_auditoria(&env));
+        while id < proxima && entradas.len() < limite {
+            if let Some(entrada) = leer_auditoria(&env, id) {
+                entradas.push_back(entrada);
+            }
+            id += 1;
+        }
+        Ok(entradas)
+    }
+
+    // Retorna: (primer id guardado, id que tendrá la próxima entrada)
+    // El primero puede estar vencido si todavía no se podó
+    pub fn get_rango_auditoria(env: Env) -> Result<(u32, u32), Error> {
+        verificar_inicializado(&env)?;
+
+        Ok((leer_primera_auditoria(&env), leer_proxima_auditoria(&env)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_retencion_auditoria
+    // ========================================================================
+    // Propósito: Cuántos ledgers se conserva cada entrada de auditoría
+    //            (SOLO ADMIN), hasta MAX_RETENCION_AUDITORIA
+    // Solo puede subir: bajarla ocultaría entradas ya escritas (también las
+    // del propio admin), y el log tiene que ser de solo agregar
+    // ========================================================================
+    pub fn set_retencion_auditoria(
+        env: Env,
+        caller: Address,
+        retencion: u32
+    ) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+
+        let anterior = leer_retencion_auditoria(&env);
+        if retencion < anterior || retencion > MAX_RETENCION_AUDITORIA {
+            return Err(ErrorExtra::RetencionInvalida.into());
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExtra::RetencionAuditoria, &retencion);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::SetRetencion,
+            None,
+            ValorAuditoria::Numero(anterior as u64),
+            ValorAuditoria::Numero(retencion as u64),
+            None
+        );
+
+        Ok(())
+    }
+
+    pub fn get_retencion_auditoria(env: Env) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_retencion_auditoria(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: transfer_admin
+    // ========================================================================
+    // Propósito: Transferir el control del contrato a otra dirección
+    // Parámetros:
+    //   - current_admin: Admin actual (debe coincidir con el guardado)
+    //   - new_admin: Dirección que pasa a ser admin
+    // ========================================================================
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address
+    ) -> Result<(), Error> {
+        // Solo el admin actual puede transferir
+        verificar_admin(&env, &SALA_GENERAL, &current_admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Admin, &new_admin);
+
+        registrar_auditoria(
+            &env,
+            current_admin.clone(),
+            AccionAuditoria::TransferAdmin,
+            None,
+            ValorAuditoria::Direccion(current_admin),
+            ValorAuditoria::Direccion(new_admin),
+            None
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_admin
+    // ========================================================================
+    // Retorna: Result<Address, Error> - el admin actual
+    //   - NoInicializado: todavía no hay admin
+    // ========================================================================
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        leer_admin(&env, &SALA_GENERAL).ok_or(Error::NoInicializado)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_limite_nombre
+    // ========================================================================
+    // Propósito: Cambiar el límite máximo de caracteres (SOLO ADMIN)
+    // El límite debe estar entre 1 y 256 caracteres
+    // ========================================================================
+    pub fn set_limite_nombre(
+        env: Env,
+        caller: Address,
+        nuevo_limite: u32
+    ) -> Result<(), Error> {
+        // Verificar permisos ANTES de validar el valor
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        validar_limite(nuevo_limite)?;
+
+        // Con timelock activo el cambio tiene que pasar por programar_limite()
+        if leer_retraso(&env) > 0 {
+            return Err(Error::RequiereProgramacion);
+        }
+
+        let anterior = leer_limite(&env, &SALA_GENERAL);
+        guardar_limite(&env, &SALA_GENERAL, nuevo_limite);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::SetLimiteNombre,
+            None,
+            ValorAuditoria::Numero(anterior as u64),
+            ValorAuditoria::Numero(nuevo_limite as u64),
+            None
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_limite_nombre
+    // ========================================================================
+    // Retorna: Result<u32, Error> - límite configurado (32 si nunca se configuró)
+    // ========================================================================
+    pub fn get_limite_nombre(env: Env) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_limite(&env, &SALA_GENERAL))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: crear_sala
+    // ========================================================================
+    // Propósito: Crear una sala con su propio admin, contador y límite
+    // (SOLO ADMIN GENERAL: si no, cualquiera podría acaparar ids)
+    // Parámetros:
+    //   - caller: El admin general
+    //   - sala: Id de la sala (Symbol, ej: "meetup")
+    //   - admin: Quién administra la sala (también firma, salvo que sea caller)
+    //   - limite: Límite de caracteres para los nombres de esta sala
+    // Retorna: Result<(), Error> - SalaYaExiste si el id está tomado
+    // ========================================================================
+    pub fn crear_sala(
+        env: Env,
+        caller: Address,
+        sala: Symbol,
+        admin: Address,
+        limite: u32
+    ) -> Result<(), Error> {
+        // PASO 0: Sin initialize (o constructor) no se crean salas
+        verificar_inicializado(&env)?;
+
+        // PASO 1: La crea el admin general, y el de la sala acepta serlo
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        if admin != caller {
+            admin.require_auth();
+        }
+
+        // PASO 2: El id no puede estar tomado (la general siempre existe)
+        let key_admin = DataKey::AdminSala(sala.clone());
+        if es_general(&sala) || env.storage().persistent().has(&key_admin) {
+            return Err(Error::SalaYaExiste);
+        }
+
+        // PASO 3: Validar el límite con las mismas reglas que la sala general
+        validar_limite(limite)?;
+
+        // PASO 4: Guardar la configuración de la sala
+        env.storage().persistent().set(&key_admin, &admin);
+        env.storage().persistent().extend_ttl(&key_admin, 100, 100);
+        guardar_contador(&env, &sala, 0);
+        guardar_limite(&env, &sala, limite);
+
+        registrar_auditoria_sala(
+            &env,
+            &sala,
+            caller,
+            AccionAuditoria::CrearSala,
+            ValorAuditoria::Ninguno,
+            ValorAuditoria::Direccion(admin),
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_en_sala
+    // ========================================================================
+    // Propósito: Igual que hello(), pero registrando el saludo en una sala
+    // Retorna: Result<String, Error> - SalaNoExiste si la sala no fue creada
+    // ========================================================================
+    pub fn hello_en_sala(
+        env: Env,
+        sala: Symbol,
+        usuario: Address,
+        nombre: String
+    ) -> Result<String, Error> {
+        usuario.require_auth();
+        saludar(&env, &sala, usuario, nombre, false)
+    }
+
+    // ========================================================================
+    // GETTERS POR SALA
+    // ========================================================================
+    // Mismas reglas que los getters legacy: NoInicializado antes de
+    // initialize(), y 0/None si la sala o el usuario no tienen datos
+    // ========================================================================
+    pub fn get_contador_sala(env: Env, sala: Symbol) -> Result<u64, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_contador(&env, &sala))
+    }
+
+    pub fn get_ultimo_saludo_sala(
+        env: Env,
+        sala: Symbol,
+        usuario: Address
+    ) -> Result<Option<String>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_ultimo_saludo(&env, &sala, usuario))
+    }
+
+    pub fn get_contador_usuario_sala(env: Env, sala: Symbol, usuario: Address) -> Result<u64, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_contador_usuario(&env, &sala, &usuario))
+    }
+
+    pub fn get_limite_sala(env: Env, sala: Symbol) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_limite(&env, &sala))
+    }
+
+    pub fn get_admin_sala(env: Env, sala: Symbol) -> Result<Option<Address>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_admin(&env, &sala))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: reset_contador_sala
+    // ========================================================================
+    // Propósito: Resetear el contador de una sala (SOLO ADMIN DE LA SALA)
+    // ========================================================================
+    pub fn reset_contador_sala(env: Env, sala: Symbol, caller: Address) -> Result<(), Error> {
+        verificar_admin(&env, &sala, &caller)?;
+        let anterior = leer_contador(&env, &sala);
+        guardar_contador(&env, &sala, 0);
+
+        registrar_auditoria_sala(
+            &env,
+            &sala,
+            caller,
+            AccionAuditoria::ResetContadorSala,
+            ValorAuditoria::Numero(anterior),
+            ValorAuditoria::Numero(0),
+        );
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_limite_sala
+    // ========================================================================
+    // Propósito: Cambiar el límite de una sala (SOLO ADMIN DE LA SALA)
+    // ========================================================================
+    pub fn set_limite_sala(
+        env: Env,
+        sala: Symbol,
+        caller: Address,
+        nuevo_limite: u32
+    ) -> Result<(), Error> {
+        verificar_admin(&env, &sala, &caller)?;
+        validar_limite(nuevo_limite)?;
+        let anterior = leer_limite(&env, &sala);
+        guardar_limite(&env, &sala, nuevo_limite);
+
+        registrar_auditoria_sala(
+            &env,
+            &sala,
+            caller,
+            AccionAuditoria::SetLimiteSala,
+            ValorAuditoria::Numero(anterior.into()),
+            ValorAuditoria::Numero(nuevo_limite.into()),
+        );
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: registrar_hook
+    // ========================================================================
+    // Propósito: Registrar un contrato que implementa SaludoHook (SOLO ADMIN)
+    // Parámetros:
+    //   - contrato: Dirección del contrato hook
+    //   - obligatorio: Si su fallo debe abortar el saludo
+    // Si el hook ya estaba registrado solo se actualiza "obligatorio"
+    // ========================================================================
+    pub fn registrar_hook(
+        env: Env,
+        caller: Address,
+        contrato: Address,
+        obligatorio: bool
+    ) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let mut hooks = leer_hooks(&env);
+        let hook = Hook { contrato: contrato.clone(), obligatorio };
+
+        match hooks.iter().position(|h| h.contrato == contrato) {
+            Some(i) => hooks.set(i as u32, hook),
+            None => {
+                if hooks.len() >= MAX_HOOKS {
+                    return Err(Error::DemasiadosHooks);
+                }
+                hooks.push_back(hook);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Hooks, &hooks);
+        env.storage().instance().extend_ttl(100, 100);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::RegistrarHook,
+            None,
+            ValorAuditoria::Ninguno,
+            ValorAuditoria::Direccion(contrato),
+            None
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: quitar_hook
+    // ========================================================================
+    // Propósito: Dejar de llamar a un hook (SOLO ADMIN)
+    // ========================================================================
+    pub fn quitar_hook(env: Env, caller: Address, contrato: Address) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let mut hooks = leer_hooks(&env);
+        let i = hooks
+            .iter()
+            .position(|h| h.contrato == contrato)
+            .ok_or(Error::HookNoExiste)?;
+        hooks.remove(i as u32);
+
+        env.storage().instance().set(&DataKey::Hooks, &hooks);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::QuitarHook,
+            None,
+            ValorAuditoria::Direccion(contrato),
+            ValorAuditoria::Ninguno,
+            None
+        );
+
+        Ok(())
+    }
+
+    // Retorna: Result<Vec<Hook>, Error> - hooks registrados en orden de ejecución
+    pub fn get_hooks(env: Env) -> Result<Vec<Hook>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_hooks(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: agregar_moderador / quitar_moderador
+    // ========================================================================
+    // Propósito: Gestionar quién puede manejar las listas (SOLO ADMIN)
+    // ========================================================================
+    pub fn agregar_moderador(env: Env, caller: Address, moderador: Address) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        let anterior = es_moderador(&env, &moderador);
+        marcar(&env, &DataKey::Moderador(moderador.clone()), true);
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::AgregarModerador,
+            Some(moderador),
+            ValorAuditoria::Booleano(anterior),
+            ValorAuditoria::Booleano(true),
+            None
+        );
+        Ok(())
+    }
+
+    pub fn quitar_moderador(env: Env, caller: Address, moderador: Address) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        let anterior = es_moderador(&env, &moderador);
+        marcar(&env, &DataKey::Moderador(moderador.clone()), false);
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::QuitarModerador,
+            Some(moderador),
+            ValorAuditoria::Booleano(anterior),
+            ValorAuditoria::Booleano(false),
+            None
+        );
+        Ok(())
+    }
+
+    pub fn es_moderador(env: Env, usuario: Address) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(es_moderador(&env, &usuario))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_modo_lista
+    // ========================================================================
+    // Propósito: Activar/desactivar el modo "solo lista de permitidos"
+    // Con el modo activo, solo las direcciones permitidas pueden saludar
+    // ========================================================================
+    pub fn set_modo_lista(env: Env, caller: Address, activo: bool) -> Result<(), Error> {
+        verificar_moderador(&env, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ModoListaPermitidos, &activo);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    pub fn get_modo_lista(env: Env) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .instance()
+            .get(&DataKey::ModoListaPermitidos)
+            .unwrap_or(false))
+    }
+
+    // ========================================================================
+    // FUNCIONES: agregar_permitidos / quitar_permitidos
+    // ========================================================================
+    // Propósito: Editar la lista de permitidos en lotes (ADMIN o MODERADOR)
+    // Parámetros:
+    //   - usuarios: Hasta MAX_LOTE direcciones por llamada
+    // ========================================================================
+    pub fn agregar_permitidos(env: Env, caller: Address, usuarios: Vec<Address>) -> Result<(), Error> {
+        verificar_moderador(&env, &caller)?;
+        validar_lote(&usuarios)?;
+
+        for usuario in usuarios.iter() {
+            marcar(&env, &DataKey::Permitido(usuario), true);
+        }
+
+        Ok(())
+    }
+
+    pub fn quitar_permitidos(env: Env, caller: Address, usuarios: Vec<Address>) -> Result<(), Error> {
+        verificar_moderador(&env, &caller)?;
+        validar_lote(&usuarios)?;
+
+        for usuario in usuarios.iter() {
+            marcar(&env, &DataKey::Permitido(usuario), false);
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIONES: bloquear / desbloquear
+    // ========================================================================
+    // Propósito: Editar la lista de bloqueados en lotes (ADMIN o MODERADOR)
+    // El bloqueo gana sobre la lista de permitidos
+    // ========================================================================
+    pub fn bloquear(env: Env, caller: Address, usuarios: Vec<Address>) -> Result<(), Error> {
+        verificar_moderador(&env, &caller)?;
+        validar_lote(&usuarios)?;
+
+        for usuario in usuarios.iter() {
+            marcar(&env, &DataKey::Bloqueado(usuario), true);
+        }
+
+        Ok(())
+    }
+
+    pub fn desbloquear(env: Env, caller: Address, usuarios: Vec<Address>) -> Result<(), Error> {
+        verificar_moderador(&env, &caller)?;
+        validar_lote(&usuarios)?;
+
+        for usuario in usuarios.iter() {
+            marcar(&env, &DataKey::Bloqueado(usuario), false);
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: esta_permitido
+    // ========================================================================
+    // Retorna: Result<bool, Error> - true si hello() aceptaría a este usuario ahora mismo
+    // ========================================================================
+    pub fn esta_permitido(env: Env, usuario: Address) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(verificar_acceso(&env, &usuario, false).is_ok())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_raiz_merkle
+    // ========================================================================
+    // Propósito: Configurar la raíz del árbol de asistentes (SOLO ADMIN)
+    // Las hojas usadas se guardan por raíz: una raíz nueva (otro evento)
+    // permite volver a saludar con prueba
+    // ========================================================================
+    pub fn set_raiz_merkle(env: Env, caller: Address, raiz: BytesN<32>) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let anterior: Option<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::RaizMerkle);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RaizMerkle, &raiz);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::SetRaizMerkle,
+            None,
+            anterior.map_or(ValorAuditoria::Ninguno, ValorAuditoria::Hash),
+            ValorAuditoria::Hash(raiz),
+            None
+        );
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    pub fn get_raiz_merkle(env: Env) -> Result<Option<BytesN<32>>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .instance()
+            .get(&DataKey::RaizMerkle))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_con_prueba
+    // ========================================================================
+    // Propósito: Saludar demostrando pertenencia a la lista Merkle
+    // Parámetros:
+    //   - usuario: Quien saluda (debe firmar: su hoja se marca como usada)
+    //   - nombre: Nombre de la Tiburona
+    //   - prueba: Hermanos desde la hoja hasta la raíz
+    // Retorna: Result<String, Error> - Saludo localizado si la prueba es válida
+    // ========================================================================
+    pub fn hello_con_prueba(
+        env: Env,
+        usuario: Address,
+        nombre: String,
+        prueba: Vec<BytesN<32>>
+    ) -> Result<String, Error> {
+        // PASO 1: Solo el dueño de la hoja puede gastarla
+        usuario.require_auth();
+
+        // PASO 2: Verificar la prueba contra la raíz configurada
+        let raiz: BytesN<32> = env.storage()
+            .instance()
+            .get(&DataKey::RaizMerkle)
+            .ok_or(Error::RaizNoConfigurada)?;
+
+        let hoja = hoja_merkle(&env, &usuario);
+        let key_hoja = DataKey::HojaUsada(raiz.clone(), hoja.clone());
+
+        if env.storage().persistent().has(&key_hoja) {
+            return Err(Error::HojaYaUsada);
+        }
+
+        verificar_prueba(&env, &raiz, &hoja, &prueba)?;
+
+        // PASO 3: Saludo normal; la prueba reemplaza a la lista de permitidos
+        let resultado = saludar(&env, &SALA_GENERAL, usuario, nombre, true)?;
+
+        // PASO 4: Marcar la hoja como usada
+        env.storage().persistent().set(&key_hoja, &true);
+        env.storage().persistent().extend_ttl(&key_hoja, 100, 100);
+
+        Ok(resultado)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_firmado
+    // ========================================================================
+    // Propósito: Registrar un saludo firmado off-chain y enviado por un relayer
+    // Parámetros:
+    //   - pubkey: Clave ed25519 de quien firma (su cuenta G... recibe el saludo)
+    //   - nombre: Nombre de la Tiburona
+    //   - nonce: Debe ser get_nonce_firma(pubkey); evita repetir la firma
+    //   - expiracion: Último ledger en el que la firma es válida
+    //   - firma: Firma ed25519 de payload_hello_firmado()
+    // Retorna: Result<String, Error> - Saludo localizado si la firma es válida
+    // Quien envía la transacción (el relayer) no necesita ser el firmante
+    // ========================================================================
+    pub fn hello_firmado(
+        env: Env,
+        pubkey: BytesN<32>,
+        nombre: String,
+        nonce: u64,
+        expiracion: u32,
+        firma: BytesN<64>
+    ) -> Result<String, Error> {
+        // VALIDACIÓN 1: La firma sigue vigente
+        if env.ledger().sequence() > expiracion {
+            return Err(Error::FirmaExpirada);
+        }
+
+        // VALIDACIÓN 2: El nonce es el siguiente para esta clave
+        let key_nonce = DataKey::NonceFirma(pubkey.clone());
+        let esperado: u64 = env.storage()
+            .persistent()
+            .get(&key_nonce)
+            .unwrap_or(0);
+
+        if nonce != esperado {
+            return Err(Error::NonceInvalido);
+        }
+
+        // VALIDACIÓN 3: La firma corresponde al payload
+        // ed25519_verify hace panic si la firma no es válida
+        let payload = payload_hello_firmado(&env, &pubkey, &nombre, nonce, expiracion);
+        env.crypto().ed25519_verify(&pubkey, &payload, &firma);
+
+        // PASO 1: Consumir el nonce
+        env.storage().persistent().set(&key_nonce, &(nonce + 1));
+        env.storage().persistent().extend_ttl(&key_nonce, 100, 100);
+
+        // PASO 2: Saludo normal acreditado a la cuenta del firmante
+        let usuario = direccion_de_clave(&env, &pubkey);
+        saludar(&env, &SALA_GENERAL, usuario, nombre, false)
+    }
+
+    // Retorna: Result<u64, Error> - nonce que debe usar la próxima firma de esta clave
+    pub fn get_nonce_firma(env: Env, pubkey: BytesN<32>) -> Result<u64, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::NonceFirma(pubkey))
+            .unwrap_or(0))
+    }
+
+    // Retorna: Bytes - el mensaje exacto que el usuario debe firmar
+    pub fn get_payload_firma(
+        env: Env,
+        pubkey: BytesN<32>,
+        nombre: String,
+        nonce: u64,
+        expiracion: u32
+    ) -> Bytes {
+        payload_hello_firmado(&env, &pubkey, &nombre, nonce, expiracion)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: registrar_passkey
+    // ========================================================================
+    // Propósito: Vincular una passkey (clave pública secp256r1 sin comprimir,
+    //            65 bytes: 0x04 || X || Y) a la dirección del usuario
+    // El usuario firma la transacción una vez; después puede saludar solo
+    // con la passkey usando hello_passkey()
+    // ========================================================================
+    pub fn registrar_passkey(env: Env, usuario: Address, pubkey: BytesN<65>) -> Result<(), Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        let key = DataKey::Passkey(pubkey);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::PasskeyYaRegistrada);
+        }
+
+        env.storage().persistent().set(&key, &usuario);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: quitar_passkey
+    // ========================================================================
+    // Propósito: Desvincular una passkey (SOLO SU DUEÑO)
+    // El nonce se conserva: volver a registrar la misma passkey no reabre
+    // firmas viejas
+    // ========================================================================
+    pub fn quitar_passkey(env: Env, usuario: Address, pubkey: BytesN<65>) -> Result<(), Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        let key = DataKey::Passkey(pubkey);
+        let duenio: Address = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PasskeyNoRegistrada)?;
+
+        if duenio != usuario {
+            return Err(Error::NoAutorizado);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    pub fn get_usuario_passkey(env: Env, pubkey: BytesN<65>) -> Result<Option<Address>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::Passkey(pubkey)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_passkey
+    // ========================================================================
+    // Propósito: Saludar autenticando con una passkey registrada
+    // Parámetros:
+    //   - pubkey: Passkey vinculada con registrar_passkey()
+    //   - nombre: Nombre de la Tiburona
+    //   - nonce: Debe ser get_nonce_passkey(pubkey)
+    //   - authenticator_data: response.authenticatorData de la aserción
+    //   - client_data_json: response.clientDataJSON, con
+    //            get_challenge_passkey(nombre, nonce) como challenge
+    //   - firma: response.signature convertida de DER a r || s (s normalizado)
+    // Retorna: Result<String, soroban_sdk::Error> - Saludo en el idioma del
+    //          dueño de la passkey
+    // ========================================================================
+    pub fn hello_passkey(
+        env: Env,
+        pubkey: BytesN<65>,
+        nombre: String,
+        nonce: u64,
+        authenticator_data: Bytes,
+        client_data_json: Bytes,
+        firma: BytesN<64>
+    ) -> Result<String, soroban_sdk::Error> {
+        // PASO 1: Buscar al usuario vinculado a la passkey
+        let usuario: Address = env.storage()
+            .persistent()
+            .get(&DataKey::Passkey(pubkey.clone()))
+            .ok_or(Error::PasskeyNoRegistrada)?;
+
+        // PASO 2: Verificar el nonce de la passkey
+        let key_nonce = DataKey::NoncePasskey(pubkey.clone());
+        let esperado: u64 = env.storage()
+            .persistent()
+            .get(&key_nonce)
+            .unwrap_or(0);
+
+        if nonce != esperado {
+            return Err(Error::NonceInvalido.into());
+        }
+
+        // PASO 3: Verificar la firma sobre el mensaje WebAuthn
+        // El autenticador firma authenticatorData || sha256(clientDataJSON);
+        // secp256r1_verify hace panic si la firma no es válida
+        let challenge = challenge_passkey(&env, &nombre, nonce);
+        let mensaje = mensaje_webauthn(&env, &challenge, &authenticator_data, &client_data_json)?;
+        let digest = env.crypto().sha256(&mensaje);
+        env.crypto().secp256r1_verify(&pubkey, &digest, &firma);
+
+        // PASO 4: Consumir el nonce y saludar a nombre del usuario
+        env.storage().persistent().set(&key_nonce, &(nonce + 1));
+        env.storage().persistent().extend_ttl(&key_nonce, 100, 100);
+
+        Ok(saludar(&env, &SALA_GENERAL, usuario, nombre, false)?)
+    }
+
+    pub fn get_nonce_passkey(env: Env, pubkey: BytesN<65>) -> Result<u64, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::NoncePasskey(pubkey))
+            .unwrap_or(0))
+    }
+
+    // Retorna: BytesN<32> - el challenge que el front-end pasa a
+    //          navigator.credentials.get() para firmar este nombre y nonce
+    pub fn get_challenge_passkey(env: Env, nombre: String, nonce: u64) -> BytesN<32> {
+        challenge_passkey(&env, &nombre, nonce)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: configurar_consejo
+    // ========================================================================
+    // Propósito: Pasar del admin único a un consejo de N firmantes con umbral M
+    // Solo el admin y solo una vez: después los cambios de firmantes se hacen
+    // con una propuesta AccionConsejo::CambiarConsejo
+    // ========================================================================
+    pub fn configurar_consejo(env: Env, caller: Address, consejo: Consejo) -> Result<(), Error> {
+        // verificar_admin() exige la firma de caller: sin ella cualquiera
+        // podría instalar un consejo propio y dejar afuera al admin real
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        validar_consejo(&consejo)?;
+
+        env.storage().instance().set(&DataKey::Consejo, &consejo);
+        env.storage().instance().extend_ttl(100, 100);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::ConfigurarConsejo,
+            None,
+            ValorAuditoria::Ninguno,
+            ValorAuditoria::Consejo(consejo),
+            None
+        );
+
+        Ok(())
+    }
+
+    pub fn get_consejo(env: Env) -> Result<Option<Consejo>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_consejo(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: proponer
+    // ========================================================================
+    // Propósito: Crear una propuesta del consejo (SOLO FIRMANTES)
+    // Parámetros:
+    //   - firmante: Quien propone (su aprobación cuenta automáticamente)
+    //   - accion: Acción privilegiada a ejecutar
+    //   - duracion: Ledgers durante los que se puede aprobar
+    // Retorna: Result<u32, Error> - id de la propuesta
+    // Con umbral 1 la propuesta se ejecuta en el mismo llamado
+    // ========================================================================
+    pub fn proponer(
+        env: Env,
+        firmante: Address,
+        accion: AccionConsejo,
+        duracion: u32
+    ) -> Result<u32, Error> {
+        firmante.require_auth();
+        let consejo = verificar_firmante(&env, &firmante)?;
+        validar_accion(&accion)?;
+
+        // Liberar lugar: las propuestas vencidas salen de la lista pendiente
+        let ahora = env.ledger().sequence();
+        let mut pendientes = Vec::new(&env);
+        for id in leer_pendientes(&env).iter() {
+            if leer_propuesta(&env, id).is_ok_and(|p| p.expira >= ahora) {
+                pendientes.push_back(id);
+            }
+        }
+        if pendientes.len() >= MAX_PROPUESTAS {
+            return Err(Error::DemasiadasPropuestas);
+        }
+
+        let id: u32 = env.storage()
+            .instance()
+            .get(&DataKey::ProximaPropuesta)
+            .unwrap_or(1);
+
+        let propuesta = Propuesta {
+            id,
+            accion,
+            proponente: firmante.clone(),
+            aprobaciones: vec![&env, firmante.clone()],
+            expira: ahora.saturating_add(duracion),
+            estado: EstadoPropuesta::Pendiente,
+        };
+
+        pendientes.push_back(id);
+        env.storage().instance().set(&DataKey::ProximaPropuesta, &(id + 1));
+        env.storage().instance().set(&DataKey::PropuestasPendientes, &pendientes);
+        env.storage().instance().extend_ttl(100, 100);
+        guardar_propuesta(&env, &propuesta);
+
+        if consejo.umbral <= 1 {
+            ejecutar_propuesta(&env, &firmante, propuesta)?;
+        }
+
+        Ok(id)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: aprobar
+    // ========================================================================
+    // Propósito: Sumar la aprobación de un firmante a una propuesta
+    // Retorna: Result<bool, Error> - true si con esta aprobación se ejecutó
+    // ========================================================================
+    pub fn aprobar(env: Env, firmante: Address, id: u32) -> Result<bool, Error> {
+        firmante.require_auth();
+        let consejo = verificar_firmante(&env, &firmante)?;
+
+        let mut propuesta = leer_propuesta(&env, id)?;
+
+        if propuesta.estado != EstadoPropuesta::Pendiente {
+            return Err(Error::PropuestaCerrada);
+        }
+        if env.ledger().sequence() > propuesta.expira {
+            return Err(Error::PropuestaExpirada);
+        }
+        if propuesta.aprobaciones.contains(&firmante) {
+            return Err(Error::YaAprobada);
+        }
+
+        propuesta.aprobaciones.push_back(firmante.clone());
+
+        if propuesta.aprobaciones.len() >= consejo.umbral {
+            ejecutar_propuesta(&env, &firmante, propuesta)?;
+            return Ok(true);
+        }
+
+        guardar_propuesta(&env, &propuesta);
+        Ok(false)
+    }
+
+    pub fn get_propuesta(env: Env, id: u32) -> Result<Option<Propuesta>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_propuesta(&env, id).ok())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_propuestas_pendientes
+    // ========================================================================
+    // Retorna: Result<Vec<Propuesta>, Error> - propuestas abiertas que todavía no vencieron
+    // ========================================================================
+    pub fn get_propuestas_pendientes(env: Env) -> Result<Vec<Propuesta>, Error> {
+        verificar_inicializado(&env)?;
+
+        let ahora = env.ledger().sequence();
+        let mut resultado = Vec::new(&env);
+
+        for id in leer_pendientes(&env).iter() {
+            if let Ok(propuesta) = leer_propuesta(&env, id) {
+                if propuesta.expira >= ahora {
+                    resultado.push_back(propuesta);
+                }
+            }
+        }
+
+        Ok(resultado)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: programar_limite
+    // ========================================================================
+    // Propósito: Poner en cola un nuevo límite de nombre (SOLO ADMIN)
+    // Retorna: Result<u32, Error> - id del cambio programado
+    // El límite se valida ahora para no encolar algo que fallaría después
+    // ========================================================================
+    pub fn programar_limite(env: Env, caller: Address, nuevo_limite: u32) -> Result<u32, Error> {
+        validar_limite(nuevo_limite)?;
+        programar_cambio(&env, &caller, CambioConfig::LimiteNombre(nuevo_limite))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: programar_retraso
+    // ========================================================================
+    // Propósito: Poner en cola un nuevo retraso del timelock (SOLO ADMIN)
+    // Usa el retraso actual: bajarlo a 0 también hay que esperarlo
+    // ========================================================================
+    pub fn programar_retraso(env: Env, caller: Address, ledgers: u32) -> Result<u32, Error> {
+        if ledgers > MAX_RETRASO {
+            return Err(Error::RetrasoInvalido);
+        }
+        programar_cambio(&env, &caller, CambioConfig::Retraso(ledgers))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: ejecutar_cambio
+    // ========================================================================
+    // Propósito: Aplicar un cambio cuyo retraso ya pasó
+    // Cualquiera puede llamarla: el admin ya decidió al programarlo
+    // ========================================================================
+    pub fn ejecutar_cambio(env: Env, id: u32) -> Result<(), Error> {
+        verificar_inicializado(&env)?;
+
+        let programado = leer_cambios(&env)
+            .iter()
+            .find(|c| c.id == id)
+            .ok_or(Error::CambioNoExiste)?;
+
+        if env.ledger().sequence() < programado.ejecutable_desde {
+            return Err(Error::CambioNoListo);
+        }
+
+        sacar_cambio(&env, id)?;
+
+        let (accion, anterior, nuevo) = match programado.cambio {
+            CambioConfig::LimiteNombre(limite) => {
+                let anterior = leer_limite(&env, &SALA_GENERAL);
+                guardar_limite(&env, &SALA_GENERAL, limite);
+                (AccionAuditoria::LimitePorTimelock, anterior, limite)
+            }
+            CambioConfig::Retraso(ledgers) => {
+                let anterior = leer_retraso(&env);
+                env.storage().instance().set(&DataKey::RetrasoCambios, &ledgers);
+                (AccionAuditoria::RetrasoPorTimelock, anterior, ledgers)
+            }
+        };
+        env.storage().instance().extend_ttl(100, 100);
+
+        CambioEjecutado { id, cambio: programado.cambio }.publish(&env);
+
+        // Sin firmante: el admin decidió al programarlo
+        registrar_auditoria(
+            &env,
+            env.current_contract_address(),
+            accion,
+            None,
+            ValorAuditoria::Numero(anterior as u64),
+            ValorAuditoria::Numero(nuevo as u64),
+            None
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: cancelar_cambio
+    // ========================================================================
+    // Propósito: Sacar un cambio de la cola sin aplicarlo (SOLO ADMIN)
+    // Con consejo se cancela con AccionConsejo::CancelarCambio: el admin
+    // solo no puede deshacer lo que decidió el consejo
+    // Retorna: Result<(), Error>
+    //   - NoAutorizado: el cambio salió de una votación aprobada
+    // ========================================================================
+    pub fn cancelar_cambio(env: Env, caller: Address, id: u32) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        verificar_sin_consejo(&env)?;
+        cancelar(&env, id)
+    }
+
+    // Retorna: Result<Vec<CambioProgramado>, Error> - cambios en cola, en orden de creación
+    pub fn get_cambios_programados(env: Env) -> Result<Vec<CambioProgramado>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_cambios(&env))
+    }
+
+    pub fn get_retraso_cambios(env: Env) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_retraso(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: configurar_gobernanza
+    // ========================================================================
+    // Propósito: Ajustar quórum y período de las votaciones (SOLO ADMIN)
+    // Solo afecta a las votaciones creadas después del cambio
+    // ========================================================================
+    pub fn configurar_gobernanza(
+        env: Env,
+        caller: Address,
+        config: ConfigGobernanza
+    ) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if config.quorum == 0 || config.periodo == 0 || config.periodo > MAX_RETRASO {
+            return Err(Error::GobernanzaInvalida);
+        }
+
+        env.storage().instance().set(&DataKey::ConfigGobernanza, &config);
+        env.storage().instance().extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    pub fn get_gobernanza(env: Env) -> Result<ConfigGobernanza, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_gobernanza(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_info
+    // ========================================================================
+    // Propósito: Autodescripción del contrato desplegado
+    // Retorna: Result<InfoContrato, Error> - versión, funcionalidades, modos activos,
+    //          versión del esquema y tabla de códigos de error
+    // ========================================================================
+    pub fn get_info(env: Env) -> Result<InfoContrato, Error> {
+        verificar_inicializado(&env)?;
+
+        let mut funcionalidades = Vec::new(&env);
+        for nombre in FUNCIONALIDADES {
+            funcionalidades.push_back(Symbol::new(&env, nombre));
+        }
+
+        // Modos opcionales que dependen de la configuración de esta instancia
+        let storage = env.storage().instance();
+        let modos = [
+            ("lista_permitidos", storage.get(&DataKey::ModoListaPermitidos).unwrap_or(false)),
+            ("merkle", storage.has(&DataKey::RaizMerkle)),
+            ("hooks", !leer_hooks(&env).is_empty()),
+            ("consejo", leer_consejo(&env).is_some()),
+            ("timelock", leer_retraso(&env) > 0),
+            ("gobernanza", storage.has(&DataKey::ConfigGobernanza)),
+            ("palabras_prohibidas", !leer_palabras_prohibidas(&env).is_empty()),
+            ("registro_nombres", storage.get(&DataKey::RequiereNombreRegistrado).unwrap_or(false)),
+            ("saturar_contadores", leer_modo_desbordamiento(&env) == ModoDesbordamiento::Saturar),
+        ];
+        let mut activas = Vec::new(&env);
+        for (nombre, activa) in modos {
+            if activa {
+                activas.push_back(Symbol::new(&env, nombre));
+            }
+        }
+
+        let mut errores = Vec::new(&env);
+        for (codigo, nombre) in TABLA_ERRORES {
+            errores.push_back(CodigoError { codigo, nombre: Symbol::new(&env, nombre) });
+        }
+
+        Ok(InfoContrato {
+            version: VERSION_CONTRATO,
+            funcionalidades,
+            activas,
+            version_esquema: leer_version_esquema(&env),
+            errores,
+        })
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_modo_desbordamiento
+    // ========================================================================
+    // Propósito: Elegir qué pasa cuando un contador llega a u64::MAX
+    //            (solo ADMIN): Fallar (por defecto) o Saturar
+    // ========================================================================
+    pub fn set_modo_desbordamiento(
+        env: Env,
+        caller: Address,
+        modo: ModoDesbordamiento
+    ) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let anterior = leer_modo_desbordamiento(&env);
+        env.storage()
+            .instance()
+            .set(&DataKeyExtra::ModoDesbordamiento, &modo);
+
+        registrar_auditoria(
+            &env,
+            caller,
+            AccionAuditoria::SetModoDesbordamiento,
+            None,
+            ValorAuditoria::Numero(anterior as u64),
+            ValorAuditoria::Numero(modo as u64),
+            None
+        );
+
+        Ok(())
+    }
+
+    pub fn get_modo_desbordamiento(env: Env) -> Result<ModoDesbordamiento, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_modo_desbordamiento(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: migrar_esquema
+    // ========================================================================
+    // Propósito: Pasar un contrato del esquema 1 (contadores u32) al 2 (u64)
+    //            (solo ADMIN). Reescribe ContadorSaludos como u64.
+    // Los contadores en persistent se migran solos al escribirse, o por
+    // lotes con migrar_contadores_usuarios()
+    // Retorna: Result<u32, Error> - la versión del esquema ya migrado
+    // ========================================================================
+    pub fn migrar_esquema(env: Env, caller: Address) -> Result<u32, Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if leer_version_esquema(&env) < VERSION_ESQUEMA {
+            let contador = leer_contador(&env, &SALA_GENERAL);
+            guardar_contador(&env, &SALA_GENERAL, contador);
+
+            env.storage()
+                .instance()
+                .set(&DataKeyExtra::VersionEsquema, &VERSION_ESQUEMA);
+        }
+
+        Ok(VERSION_ESQUEMA)
+    }
+
+    // Reescribe como u64 el ContadorPorUsuario de cada usuario (solo ADMIN)
+    pub fn migrar_contadores_usuarios(
+        env: Env,
+        caller: Address,
+        usuarios: Vec<Address>
+    ) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        validar_lote(&usuarios)?;
+
+        for usuario in usuarios.iter() {
+            let key = DataKey::ContadorPorUsuario(usuario.clone());
+            if env.storage().persistent().has(&key) {
+                let contador = leer_contador_usuario(&env, &SALA_GENERAL, &usuario);
+                env.storage().persistent().set(&key, &contador);
+                env.storage().persistent().extend_ttl(&key, 100, 100);
+            }
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: proponer_limite
+    // ========================================================================
+    // Propósito: Abrir una votación para cambiar el límite de nombre
+    // Parámetros:
+    //   - proponente: Debe haber saludado al menos una vez
+    //   - limite: Valor propuesto, dentro de 1..=256
+    // Retorna: Result<u32, Error> - id de la votación
+    // ========================================================================
+    pub fn proponer_limite(env: Env, proponente: Address, limite: u32) -> Result<u32, Error> {
+        proponente.require_auth();
+        validar_limite(limite)?;
+
+        let inicio = env.ledger().sequence();
+        if peso_de_voto(&env, &proponente, inicio.saturating_add(1)) == 0 {
+            return Err(Error::SinPesoDeVoto);
+        }
+
+        let id: u32 = env.storage()
+            .instance()
+            .get(&DataKey::ProximaVotacion)
+            .unwrap_or(1);
+
+        let gobernanza = leer_gobernanza(&env);
+        let votacion = Votacion {
+            id,
+            proponente,
+            limite,
+            inicio,
+            fin: inicio.saturating_add(gobernanza.periodo),
+            quorum: gobernanza.quorum,
+            a_favor: 0,
+            en_contra: 0,
+            estado: EstadoVotacion::Abierta,
+        };
+
+        // A partir de ahora saludar() empieza (o sigue) guardando checkpoints
+        env.storage().instance().set(&DataKey::UltimaVotacion, &inicio);
+        env.storage().instance().set(&DataKey::ProximaVotacion, &(id + 1));
+        env.storage().instance().extend_ttl(100, 100);
+        guardar_votacion(&env, &votacion);
+
+        Ok(id)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: votar
+    // ========================================================================
+    // Propósito: Votar a favor o en contra de una votación abierta
+    // El peso es el contador del votante antes del ledger de inicio
+    // Retorna: Result<u64, Error> - peso con el que se contó el voto
+    // ========================================================================
+    pub fn votar(env: Env, votante: Address, id: u32, a_favor: bool) -> Result<u64, Error> {
+        votante.require_auth();
+
+        let mut votacion = leer_votacion(&env, id)?;
+        if votacion.estado != EstadoVotacion::Abierta || env.ledger().sequence() > votacion.fin {
+            return Err(Error::VotacionCerrada);
+        }
+
+        let key_voto = DataKey::Voto(id, votante.clone());
+        if env.storage().persistent().has(&key_voto) {
+            return Err(Error::YaVoto);
+        }
+
+        let peso = peso_de_voto(&env, &votante, votacion.inicio);
+        if peso == 0 {
+            return Err(Error::SinPesoDeVoto);
+        }
+
+        if a_favor {
+            votacion.a_favor = votacion.a_favor.saturating_add(peso);
+        } else {
+            votacion.en_contra = votacion.en_contra.saturating_add(peso);
+        }
+
+        env.storage().persistent().set(&key_voto, &true);
+        env.storage().persistent().extend_ttl(&key_voto, 100, 100);
+        guardar_votacion(&env, &votacion);
+
+        Ok(peso)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: finalizar_votacion
+    // ========================================================================
+    // Propósito: Cerrar una votación vencida y, si ganó, aplicar el límite
+    //            (o ponerlo en la cola del timelock si hay retraso)
+    // Cualquiera puede llamarla una vez terminado el período
+    // Retorna: Result<EstadoVotacion, Error> - Aprobada o Rechazada
+    // ========================================================================
+    pub fn finalizar_votacion(env: Env, id: u32) -> Result<EstadoVotacion, Error> {
+        let mut votacion = leer_votacion(&env, id)?;
+
+        if votacion.estado != EstadoVotacion::Abierta {
+            return Err(Error::VotacionCerrada);
+        }
+        if env.ledger().sequence() <= votacion.fin {
+            return Err(Error::VotacionAbierta);
+        }
+
+        // El quórum es el que regía al proponer, no el de ahora
+        let total = votacion.a_favor + votacion.en_contra;
+
+        votacion.estado = if total >= votacion.quorum && votacion.a_favor > votacion.en_contra {
+            // El límite ya se validó al proponer (1..=256)
+            aplicar_limite_decidido(
+                &env,
+                env.current_contract_address(),
+                AccionAuditoria::LimitePorVotacion,
+                OrigenCambio::Votacion,
+                votacion.limite
+            )?;
+            EstadoVotacion::Aprobada
+        } else {
+            EstadoVotacion::Rechazada
+        };
+
+        guardar_votacion(&env, &votacion);
+
+        Ok(votacion.estado)
+    }
+
+    pub fn get_votacion(env: Env, id: u32) -> Result<Option<Votacion>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_votacion(&env, id).ok())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: agregar_palabra_prohibida
+    // ========================================================================
+    // Propósito: Rechazar los nombres que contengan `palabra` (solo ADMIN)
+    // La comparación ignora mayúsculas/minúsculas ASCII
+    // Parámetros:
+    //   - palabra: 1..=32 bytes, se guarda en minúsculas
+    // ========================================================================
+    pub fn agregar_palabra_prohibida(env: Env, caller: Address, palabra: Bytes) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let palabra = normalizar_palabra(&env, &palabra)?;
+        let mut palabras = leer_palabras_prohibidas(&env);
+
+        if palabras.contains(&palabra) {
+            return Err(Error::PalabraInvalida);
+        }
+        if palabras.len() >= MAX_PALABRAS_PROHIBIDAS {
+            return Err(Error::DemasiadasPalabras);
+        }
+
+        palabras.push_back(palabra);
+        env.storage()
+            .instance()
+            .set(&DataKey::PalabrasProhibidas, &palabras);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    // Quita una palabra de la lista (no falla si no estaba)
+    pub fn quitar_palabra_prohibida(env: Env, caller: Address, palabra: Bytes) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        let palabra = normalizar_palabra(&env, &palabra)?;
+        let mut palabras = leer_palabras_prohibidas(&env);
+
+        if let Some(indice) = palabras.first_index_of(&palabra) {
+            palabras.remove(indice);
+            env.storage()
+                .instance()
+                .set(&DataKey::PalabrasProhibidas, &palabras);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_palabras_prohibidas(env: Env) -> Result<Vec<Bytes>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_palabras_prohibidas(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_saludo_idioma
+    // ========================================================================
+    // Propósito: Registrar o cambiar el saludo de un idioma (solo ADMIN)
+    // Parámetros:
+    //   - idioma: Código corto, ej: "es", "en", "pt"
+    //   - saludo: Palabra de saludo (1..=32 bytes, puede tener acentos)
+    // ========================================================================
+    pub fn set_saludo_idioma(
+        env: Env,
+        caller: Address,
+        idioma: Symbol,
+        saludo: String
+    ) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if saludo.is_empty() || saludo.len() > MAX_LARGO_SALUDO {
+            return Err(ErrorExtra::SaludoInvalido.into());
+        }
+
+        let mut saludos = leer_saludos(&env);
+        if !saludos.contains_key(idioma.clone()) && saludos.len() >= MAX_IDIOMAS {
+            return Err(ErrorExtra::DemasiadosIdiomas.into());
+        }
+
+        saludos.set(idioma, saludo);
+        env.storage()
+            .instance()
+            .set(&DataKey::Saludos, &saludos);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    // Quita un idioma; quienes lo preferían reciben el idioma por defecto
+    pub fn quitar_idioma(env: Env, caller: Address, idioma: Symbol) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if idioma == leer_idioma_por_defecto(&env) {
+            return Err(ErrorExtra::IdiomaPorDefecto.into());
+        }
+
+        let mut saludos = leer_saludos(&env);
+        if saludos.remove(idioma).is_none() {
+            return Err(ErrorExtra::IdiomaNoSoportado.into());
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Saludos, &saludos);
+
+        Ok(())
+    }
+
+    pub fn set_idioma_por_defecto(env: Env, caller: Address, idioma: Symbol) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if !leer_saludos(&env).contains_key(idioma.clone()) {
+            return Err(ErrorExtra::IdiomaNoSoportado.into());
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::IdiomaPorDefecto, &idioma);
+
+        Ok(())
+    }
+
+    pub fn get_idioma_por_defecto(env: Env) -> Result<Symbol, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_idioma_por_defecto(&env))
+    }
+
+    // Retorna: Result<Map<Symbol, String>, Error> - idiomas soportados con su saludo
+    pub fn get_idiomas(env: Env) -> Result<Map<Symbol, String>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_saludos(&env))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_idioma
+    // ========================================================================
+    // Propósito: El usuario elige en qué idioma lo saluda hello()
+    // Retorna: Result<(), soroban_sdk::Error> - IdiomaNoSoportado si no hay saludo
+    // ========================================================================
+    pub fn set_idioma(env: Env, usuario: Address, idioma: Symbol) -> Result<(), soroban_sdk::Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        Ok(guardar_idioma(&env, &usuario, Some(idioma))?)
+    }
+
+    pub fn get_idioma(env: Env, usuario: Address) -> Result<Option<Symbol>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::Idioma(usuario)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_perfil
+    // ========================================================================
+    // Propósito: Crear o actualizar el perfil del usuario (requiere su firma)
+    // Parámetros:
+    //   - nombre: Nombre visible, mismas reglas que el nombre de hello()
+    //   - bio: Hasta 280 bytes (vacía está bien)
+    //   - avatar: Hash del avatar, opcional
+    //   - idioma: Idioma de las respuestas (None = idioma por defecto)
+    // Retorna: Result<(), soroban_sdk::Error>
+    // `creado` se fija la primera vez y no cambia con las actualizaciones
+    // ========================================================================
+    pub fn set_perfil(
+        env: Env,
+        usuario: Address,
+        nombre: String,
+        bio: String,
+        avatar: Option<BytesN<32>>,
+        idioma: Option<Symbol>
+    ) -> Result<(), soroban_sdk::Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        // VALIDACIÓN 1: El nombre visible cumple las reglas de hello()
+        if nombre.is_empty() {
+            return Err(Error::NombreVacio.into());
+        }
+        if nombre.len() > leer_limite(&env, &SALA_GENERAL) {
+            return Err(Error::NombreMuyLargo.into());
+        }
+
+        // VALIDACIÓN 2: Bio acotada
+        if bio.len() > MAX_LARGO_BIO {
+            return Err(ErrorExtra::BioMuyLarga.into());
+        }
+
+        // PASO 1: Conservar la fecha de creación si el perfil ya existía
+        let key = DataKey::Perfil(usuario.clone());
+        let anterior: Option<Perfil> = env.storage().persistent().get(&key);
+        let creado = anterior
+            .map(|perfil| perfil.creado)
+            .unwrap_or(env.ledger().sequence());
+
+        // PASO 2: Guardar el perfil y después el idioma (que lo sincroniza)
+        let perfil = Perfil {
+            nombre,
+            bio,
+            avatar,
+            idioma: None,
+            creado,
+        };
+        env.storage().persistent().set(&key, &perfil);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        guardar_idioma(&env, &usuario, idioma)?;
+
+        Ok(())
+    }
+
+    pub fn get_perfil(env: Env, usuario: Address) -> Result<Option<Perfil>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::Perfil(usuario)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_perfil
+    // ========================================================================
+    // Propósito: Saludar con el nombre visible del perfil, sin pasar nombre
+    // Retorna: Result<String, soroban_sdk::Error> - SinPerfil si no hay perfil
+    // ========================================================================
+    pub fn hello_perfil(env: Env, usuario: Address) -> Result<String, soroban_sdk::Error> {
+        usuario.require_auth();
+
+        let perfil: Perfil = env.storage()
+            .persistent()
+            .get(&DataKey::Perfil(usuario.clone()))
+            .ok_or(ErrorExtra::SinPerfil)?;
+
+        // El límite pudo bajar después de crear el perfil: saludar() lo revisa
+        Ok(saludar(&env, &SALA_GENERAL, usuario, perfil.nombre, false)?)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_plantilla
+    // ========================================================================
+    // Propósito: Guardar una plantilla de respuesta (solo ADMIN)
+    // Parámetros:
+    //   - id: Nombre corto de la plantilla, ej: "ranking"
+    //   - plantilla: Hasta 128 bytes, con {nombre}, {contador} y
+    //                {usuario_contador} como únicos marcadores
+    // ========================================================================
+    pub fn set_plantilla(
+        env: Env,
+        caller: Address,
+        id: Symbol,
+        plantilla: String
+    ) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        validar_plantilla(&plantilla)?;
+
+        let key = DataKey::Plantilla(id);
+        env.storage().persistent().set(&key, &plantilla);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        Ok(())
+    }
+
+    pub fn quitar_plantilla(env: Env, caller: Address, id: Symbol) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Plantilla(id));
+
+        Ok(())
+    }
+
+    pub fn get_plantilla(env: Env, id: Symbol) -> Result<Option<String>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::Plantilla(id)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_plantilla
+    // ========================================================================
+    // Propósito: Igual que hello(), pero la respuesta sale de una plantilla
+    // Retorna: Result<String, soroban_sdk::Error> - ej: "Hola Ana, eres la
+    //          saludadora #42" con los contadores ya incrementados
+    // ========================================================================
+    pub fn hello_plantilla(
+        env: Env,
+        usuario: Address,
+        nombre: String,
+        plantilla: Symbol
+    ) -> Result<String, soroban_sdk::Error> {
+        usuario.require_auth();
+
+        // PASO 1: Leer la plantilla antes de tocar el estado
+        let texto: String = env.storage()
+            .persistent()
+            .get(&DataKey::Plantilla(plantilla))
+            .ok_or(ErrorExtra::PlantillaNoExiste)?;
+
+        // PASO 2: Saludo normal en la sala general
+        saludar(&env, &SALA_GENERAL, usuario.clone(), nombre.clone(), false)?;
+
+        // PASO 3: Renderizar con los contadores actualizados
+        Ok(renderizar_plantilla(
+            &env,
+            &texto,
+            &nombre,
+            leer_contador(&env, &SALA_GENERAL),
+            leer_contador_usuario(&env, &SALA_GENERAL, &usuario)
+        ))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: reclamar_nombre
+    // ========================================================================
+    // Propósito: Registrar un nombre único para el usuario (requiere su firma)
+    // Parámetros:
+    //   - nombre: Mismas reglas que hello() (límite y palabras prohibidas);
+    //             se guarda en minúsculas
+    // Retorna: Result<(), soroban_sdk::Error> - NombreOcupado / YaTieneNombre
+    // ========================================================================
+    pub fn reclamar_nombre(env: Env, usuario: Address, nombre: String) -> Result<(), soroban_sdk::Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        // VALIDACIÓN 1: Las mismas reglas que un saludo en la sala general
+        if nombre.is_empty() {
+            return Err(Error::NombreVacio.into());
+        }
+        if nombre.len() > leer_limite(&env, &SALA_GENERAL) {
+            return Err(Error::NombreMuyLargo.into());
+        }
+        verificar_palabras(&env, &nombre)?;
+
+        // VALIDACIÓN 2: Un nombre por usuario y un usuario por nombre
+        if leer_nombre_registrado(&env, &usuario).is_some() {
+            return Err(ErrorExtra::YaTieneNombre.into());
+        }
+        let nombre = normalizar_nombre(&env, &nombre);
+        if env.storage().persistent().has(&DataKey::DuenoNombre(nombre.clone())) {
+            return Err(ErrorExtra::NombreOcupado.into());
+        }
+
+        asignar_nombre(&env, &nombre, &usuario);
+
+        Ok(())
+    }
+
+    // Libera el nombre del usuario para que otro lo pueda reclamar
+    pub fn liberar_nombre(env: Env, usuario: Address) -> Result<(), soroban_sdk::Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        let nombre = leer_nombre_registrado(&env, &usuario)
+            .ok_or(ErrorExtra::SinNombreRegistrado)?;
+
+        env.storage().persistent().remove(&DataKey::DuenoNombre(nombre));
+        env.storage().persistent().remove(&DataKey::NombreRegistrado(usuario));
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: transferir_nombre
+    // ========================================================================
+    // Propósito: Pasar el nombre registrado a otra dirección
+    // Solo firma el dueño actual; `destino` no puede tener nombre propio
+    // ========================================================================
+    pub fn transferir_nombre(env: Env, usuario: Address, destino: Address) -> Result<(), soroban_sdk::Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        let nombre = leer_nombre_registrado(&env, &usuario)
+            .ok_or(ErrorExtra::SinNombreRegistrado)?;
+        if leer_nombre_registrado(&env, &destino).is_some() {
+            return Err(ErrorExtra::YaTieneNombre.into());
+        }
+
+        env.storage().persistent().remove(&DataKey::NombreRegistrado(usuario));
+        asignar_nombre(&env, &nombre, &destino);
+
+        Ok(())
+    }
+
+    // Búsqueda inversa: nombre (sin importar mayúsculas) → dueño
+    pub fn quien_es(env: Env, nombre: String) -> Result<Option<Address>, Error> {
+        verificar_inicializado(&env)?;
+
+        if nombre.len() > LIMITE_MAXIMO {
+            return Ok(None);
+        }
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::DuenoNombre(normalizar_nombre(&env, &nombre))))
+    }
+
+    pub fn get_nombre_registrado(env: Env, usuario: Address) -> Result<Option<String>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_nombre_registrado(&env, &usuario))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: set_requiere_nombre_registrado
+    // ========================================================================
+    // Propósito: Exigir que cada saludo use el nombre registrado de quien
+    //            saluda (solo ADMIN). Aplica a todas las variantes de hello
+    // ========================================================================
+    pub fn set_requiere_nombre_registrado(env: Env, caller: Address, activo: bool) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RequiereNombreRegistrado, &activo);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    pub fn get_requiere_nombre_registrado(env: Env) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .instance()
+            .get(&DataKey::RequiereNombreRegistrado)
+            .unwrap_or(false))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_efimero
+    // ========================================================================
+    // Propósito: Saludo que solo dura `ledgers` ledgers (temporary storage)
+    // Parámetros:
+    //   - ledgers: Duración, de 1 al máximo configurado por el admin
+    // Retorna: Result<String, soroban_sdk::Error> - Saludo localizado
+    // Pasa las mismas validaciones que hello() pero NO suma a los contadores
+    // ni avisa a los hooks, y no reemplaza a UltimoSaludo
+    // ========================================================================
+    pub fn hello_efimero(
+        env: Env,
+        usuario: Address,
+        nombre: String,
+        ledgers: u32
+    ) -> Result<String, soroban_sdk::Error> {
+        usuario.require_auth();
+        validar_saludo(&env, &SALA_GENERAL, &usuario, &nombre, false)?;
+
+        let maximo: u32 = env.storage()
+            .instance()
+            .get(&DataKey::MaxDuracionEfimero)
+            .unwrap_or(MAX_DURACION_EFIMERO_POR_DEFECTO);
+        if ledgers == 0 || ledgers > maximo {
+            return Err(ErrorExtra::DuracionInvalida.into());
+        }
+
+        // PASO 1: El nombre, en temporary con el TTL pedido
+        // (la red puede exigir un TTL mínimo mayor: por eso vale VenceEfimero)
+        let vence = env.ledger().sequence() + ledgers;
+        let key = DataKey::SaludoEfimero(usuario.clone());
+        env.storage().temporary().set(&key, &nombre);
+        env.storage().temporary().extend_ttl(&key, ledgers, ledgers);
+
+        // PASO 2: El vencimiento, en persistent para recordar que existió
+        let key_vence = DataKey::VenceEfimero(usuario.clone());
+        env.storage().persistent().set(&key_vence, &vence);
+        env.storage().persistent().extend_ttl(&key_vence, 100, 100);
+
+        Ok(saludo_para(&env, &usuario))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_saludo_efimero
+    // ========================================================================
+    // Retorna: EstadoEfimero
+    //   - Vigente(nombre) si todavía no pasó el ledger de vencimiento
+    //   - Vencido si saludó pero ya venció
+    //   - NuncaSaludo si nunca usó hello_efimero()
+    // ========================================================================
+    pub fn get_saludo_efimero(env: Env, usuario: Address) -> Result<EstadoEfimero, Error> {
+        verificar_inicializado(&env)?;
+
+        let vence: Option<u32> = env.storage()
+            .persistent()
+            .get(&DataKey::VenceEfimero(usuario.clone()));
+
+        Ok(match vence {
+            None => EstadoEfimero::NuncaSaludo,
+            // Se mira el vencimiento antes de leer el temporary
+            Some(vence) if env.ledger().sequence() > vence => EstadoEfimero::Vencido,
+            Some(_) => env.storage()
+                .temporary()
+                .get(&DataKey::SaludoEfimero(usuario))
+                .map(EstadoEfimero::Vigente)
+                .unwrap_or(EstadoEfimero::Vencido),
+        })
+    }
+
+    // Tope de ledgers que puede pedir hello_efimero() (solo ADMIN)
+    // No puede pasar el TTL máximo de la red, ni MAX_DURACION_EFIMERO si la
+    // red permite más
+    pub fn set_max_duracion_efimero(env: Env, caller: Address, maximo: u32) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if maximo == 0 || maximo > MAX_DURACION_EFIMERO.min(env.storage().max_ttl()) {
+            return Err(ErrorExtra::DuracionInvalida.into());
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDuracionEfimero, &maximo);
+
+        Ok(())
+    }
+
+    pub fn get_max_duracion_efimero(env: Env) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .instance()
+            .get(&DataKey::MaxDuracionEfimero)
+            .unwrap_or(MAX_DURACION_EFIMERO_POR_DEFECTO))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: comprometer
+    // ========================================================================
+    // Propósito: Guardar un saludo oculto para revelarlo más tarde
+    // Parámetros:
+    //   - hash: sha256(nombre || salt), calculado fuera de la cadena
+    // Retorna: Result<Compromiso, Error> - con la ventana para revelar
+    // Un nuevo compromiso reemplaza al anterior sin revelar
+    // ========================================================================
+    pub fn comprometer(env: Env, usuario: Address, hash: BytesN<32>) -> Result<Compromiso, Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        let ventana = leer_ventana_revelacion(&env);
+        let ahora = env.ledger().sequence();
+        let compromiso = Compromiso {
+            hash,
+            desde: ahora + ventana.espera,
+            hasta: ahora + ventana.espera + ventana.duracion,
+        };
+
+        // Vive justo hasta que cierra la ventana
+        let key = DataKeyExtra::Compromiso(usuario);
+        let ttl = compromiso.hasta - ahora;
+        env.storage().temporary().set(&key, &compromiso);
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+
+        Ok(compromiso)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: revelar
+    // ========================================================================
+    // Propósito: Mostrar el nombre comprometido y saludar con él
+    // Retorna: Result<String, soroban_sdk::Error> - el saludo de hello()
+    //   - CompromisoNoExiste: nunca se comprometió o la ventana cerró
+    //   - RevelacionTemprana: todavía no abrió la ventana
+    //   - RevelacionInvalida: nombre o salt no coinciden con el hash
+    // ========================================================================
+    pub fn revelar(
+        env: Env,
+        usuario: Address,
+        nombre: String,
+        salt: Bytes
+    ) -> Result<String, soroban_sdk::Error> {
+        usuario.require_auth();
+
+        // VALIDACIÓN 1: Hay un compromiso y la ventana está abierta
+        let compromiso = leer_compromiso(&env, &usuario)
+            .ok_or(ErrorExtra::CompromisoNoExiste)?;
+        if env.ledger().sequence() < compromiso.desde {
+            return Err(ErrorExtra::RevelacionTemprana.into());
+        }
+
+        // VALIDACIÓN 2: El nombre revelado es el comprometido
+        if hash_compromiso(&env, &nombre, &salt) != compromiso.hash {
+            return Err(ErrorExtra::RevelacionInvalida.into());
+        }
+
+        // PASO 1: El compromiso se usa una sola vez
+        env.storage()
+            .temporary()
+            .remove(&DataKeyExtra::Compromiso(usuario.clone()));
+
+        // PASO 2: Saludo normal (contadores, hooks, etc.)
+        Ok(saludar(&env, &SALA_GENERAL, usuario, nombre, false)?)
+    }
+
+    pub fn get_compromiso(env: Env, usuario: Address) -> Result<Option<Compromiso>, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_compromiso(&env, &usuario))
+    }
+
+    // Ventana de revelación para los próximos compromisos (solo ADMIN)
+    pub fn set_ventana_revelacion(
+        env: Env,
+        caller: Address,
+        ventana: VentanaRevelacion
+    ) -> Result<(), soroban_sdk::Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+
+        if ventana.duracion == 0 || ventana.espera.saturating_add(ventana.duracion) > MAX_RETRASO {
+            return Err(ErrorExtra::DuracionInvalida.into());
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKeyExtra::VentanaRevelacion, &ventana);
+
+        Ok(())
+    }
+
+    pub fn get_ventana_revelacion(env: Env) -> Result<VentanaRevelacion, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(leer_ventana_revelacion(&env))
+    }
+
+    // ========================================================================
+    // FUNCIONES: agregar_operador / quitar_operador
+    // ========================================================================
+    // Propósito: Dar o quitar el rol de operador (solo ADMIN)
+    // Un operador firma hello_lote() en nombre de los asistentes
+    // ========================================================================
+    pub fn agregar_operador(env: Env, caller: Address, operador: Address) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        marcar(&env, &DataKeyExtra::Operador(operador), true);
+        Ok(())
+    }
+
+    pub fn quitar_operador(env: Env, caller: Address, operador: Address) -> Result<(), Error> {
+        verificar_admin(&env, &SALA_GENERAL, &caller)?;
+        marcar(&env, &DataKeyExtra::Operador(operador), false);
+        Ok(())
+    }
+
+    pub fn es_operador(env: Env, usuario: Address) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .has(&DataKeyExtra::Operador(usuario)))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: hello_lote
+    // ========================================================================
+    // Propósito: Registrar muchos saludos en una sola transacción (check-in)
+    // Parámetros:
+    //   - operador: Some(operador registrado) firma por todos;
+    //               None = cada usuario del lote tiene que firmar
+    //   - entradas: Hasta MAX_LOTE pares (usuario, nombre)
+    // Retorna: Result<Vec<u32>, Error> - un código por entrada, en orden:
+    //   0 = saludo registrado, otro = código de Error (ej: 1 = NombreVacio)
+    // Una entrada inválida no frena al resto. El contador de la sala se
+    // guarda una sola vez al final; los hooks se llaman por cada saludo
+    // y si uno obligatorio falla se revierte el lote completo.
+    // ========================================================================
+    pub fn hello_lote(
+        env: Env,
+        operador: Option<Address>,
+        entradas: Vec<(Address, String)>
+    ) -> Result<Vec<u32>, Error> {
+        verificar_inicializado(&env)?;
+        if entradas.len() > MAX_LOTE {
+            return Err(Error::LoteMuyGrande);
+        }
+
+        // PASO 1: Autorización (del operador, o de cada usuario una vez)
+        match operador {
+            Some(operador) => {
+                operador.require_auth();
+                if !env.storage().persistent().has(&DataKeyExtra::Operador(operador)) {
+                    return Err(Error::NoAutorizado);
+                }
+            }
+            None => {
+                let mut firmados: Vec<Address> = Vec::new(&env);
+                for (usuario, _) in entradas.iter() {
+                    if !firmados.contains(&usuario) {
+                        usuario.require_auth();
+                        firmados.push_back(usuario);
+                    }
+                }
+            }
+        }
+
+        // PASO 2: Validar y registrar cada entrada por separado
+        let mut contador = leer_contador(&env, &SALA_GENERAL);
+        let mut codigos: Vec<u32> = Vec::new(&env);
+        let mut registrados: Vec<(Address, String, u64)> = Vec::new(&env);
+
+        for (usuario, nombre) in entradas.iter() {
+            // Un desbordamiento también es un error de esa entrada sola
+            let resultado = validar_saludo(&env, &SALA_GENERAL, &usuario, &nombre, false)
+                .and_then(|()| incrementar(&env, contador))
+                .and_then(|siguiente| {
+                    registrar_saludo_usuario(&env, &SALA_GENERAL, &usuario, &nombre)?;
+                    Ok(siguiente)
+                });
+
+            match resultado {
+                Ok(siguiente) => {
+                    contador = siguiente;
+                    registrados.push_back((usuario, nombre, contador));
+                    codigos.push_back(LOTE_OK);
+                }
+                Err(error) => codigos.push_back(error as u32),
+            }
+        }
+
+        // PASO 3: Un solo write del contador de la sala
+        guardar_contador(&env, &SALA_GENERAL, contador);
+
+        env.storage()
+            .instance()
+            .extend_ttl(100, 100);
+
+        // PASO 4: Hooks, cada uno con el número de su saludo
+        for (usuario, nombre, numero) in registrados.iter() {
+            ejecutar_hooks(&env, &usuario, &nombre, numero)?;
+        }
+
+        Ok(codigos)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: ocultar_saludo
+    // ========================================================================
+    // Propósito: Ocultar el último saludo de un usuario (ADMIN o MODERADOR)
+    // El saludo se conserva: get_ultimo_saludo retorna "[oculto]" hasta que
+    // el usuario vuelva a saludar o se acepte su apelación
+    // Retorna: Result<u32, Error> - id de la acción de moderación
+    // ========================================================================
+    pub fn ocultar_saludo(
+        env: Env,
+        moderador: Address,
+        usuario: Address,
+        motivo: MotivoModeracion
+    ) -> Result<u32, Error> {
+        verificar_moderador(&env, &moderador)?;
+
+        if !env.storage().persistent().has(&DataKey::UltimoSaludo(usuario.clone())) {
+            return Err(Error::SinSaludo);
+        }
+
+        marcar(&env, &DataKey::SaludoOculto(usuario.clone()), true);
+
+        Ok(registrar_moderacion(&env, moderador, usuario, TipoModeracion::Ocultar, motivo, 0))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: borrar_saludo
+    // ========================================================================
+    // Propósito: Borrar el último saludo de un usuario (ADMIN o MODERADOR)
+    // No se puede deshacer: una apelación aceptada solo queda registrada
+    // ========================================================================
+    pub fn borrar_saludo(
+        env: Env,
+        moderador: Address,
+        usuario: Address,
+        motivo: MotivoModeracion
+    ) -> Result<u32, Error> {
+        verificar_moderador(&env, &moderador)?;
+
+        let key = DataKey::UltimoSaludo(usuario.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::SinSaludo);
+        }
+
+        env.storage().persistent().remove(&key);
+        env.storage().persistent().remove(&DataKey::SaludoOculto(usuario.clone()));
+
+        Ok(registrar_moderacion(&env, moderador, usuario, TipoModeracion::Borrar, motivo, 0))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: suspender_usuario
+    // ========================================================================
+    // Propósito: Impedir que un usuario salude durante `ledgers` ledgers
+    // ========================================================================
+    pub fn suspender_usuario(
+        env: Env,
+        moderador: Address,
+        usuario: Address,
+        ledgers: u32,
+        motivo: MotivoModeracion
+    ) -> Result<u32, Error> {
+        verificar_moderador(&env, &moderador)?;
+
+        let hasta = env.ledger().sequence().saturating_add(ledgers);
+        let key = DataKey::Suspendido(usuario.clone());
+        env.storage().persistent().set(&key, &hasta);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        Ok(registrar_moderacion(&env, moderador, usuario, TipoModeracion::Suspender, motivo, hasta))
+    }
+
+    // Retorna: Result<bool, Error> - true si el saludo del usuario está oculto
+    pub fn saludo_oculto(env: Env, usuario: Address) -> Result<bool, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .has(&DataKey::SaludoOculto(usuario)))
+    }
+
+    // Retorna: Result<u32, Error> - último ledger de suspensión (0 si nunca fue suspendido)
+    pub fn get_suspension(env: Env, usuario: Address) -> Result<u32, Error> {
+        verificar_inicializado(&env)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::Suspendido(usuario))
+            .unwrap_or(0))
+    }
+
+    // ========================================================================
+    // FUNCIÓN: get_moderaciones
+    // ========================================================================
+    // Retorna: Result<Vec<AccionModeracion>, Error> - acciones sobre el usuario, de la más
+    //          vieja a la más nueva
+    // ========================================================================
+    pub fn get_moderaciones(env: Env, usuario: Address) -> Result<Vec<AccionModeracion>, Error> {
+        verificar_inicializado(&env)?;
+
+        let ids: Vec<u32> = env.storage()
+            .persistent()
+            .get(&DataKey::LogModeracion(usuario))
+            .unwrap_or(Vec::new(&env));
+
+        let mut acciones = Vec::new(&env);
+        for id in ids.iter() {
+            if let Ok(accion) = leer_moderacion(&env, id) {
+                acciones.push_back(accion);
+            }
+        }
+        Ok(acciones)
+    }
+
+    // ========================================================================
+    // FUNCIÓN: apelar
+    // ========================================================================
+    // Propósito: El usuario afectado apela una acción (una vez por acción)
+    // Parámetros:
+    //   - mensaje: Explicación para los moderadores (1..=256 caracteres)
+    // ========================================================================
+    pub fn apelar(env: Env, usuario: Address, id: u32, mensaje: String) -> Result<(), Error> {
+        usuario.require_auth();
+        verificar_inicializado(&env)?;
+
+        if mensaje.is_empty() || mensaje.len() > MAX_APELACION {
+            return Err(Error::ApelacionInvalida);
+        }
+
+        let mut accion = leer_moderacion(&env, id)?;
+        if accion.usuario != usuario {
+            return Err(Error::NoAutorizado);
+        }
+        if accion.apelacion != EstadoApelacion::SinApelar {
+            return Err(Error::YaApelada);
+        }
+
+        accion.apelacion = EstadoApelacion::Pendiente;
+        accion.mensaje_apelacion = Some(mensaje);
+        guardar_moderacion(&env, &accion);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // FUNCIÓN: resolver_apelacion
+    // ========================================================================
+    // Propósito: Aceptar o rechazar una apelación (ADMIN o MODERADOR)
+    // Aceptar revierte la acción cuando se puede: muestra el saludo oculto
+    // o levanta la suspensión. Un borrado no se puede revertir.
+    // ========================================================================
+    pub fn resolver_apelacion(
+        env: Env,
+        moderador: Address,
+        id: u32,
+        aceptada: bool
+    ) -> Result<(), Error> {
+        verificar_moderador(&env, &moderador)?;
+
+        let mut accion = leer_moderacion(&env, id)?;
+        if accion.apelacion != EstadoApelacion::Pendiente {
+            return Err(Error::ApelacionNoPendiente);
+        }
+
+        if aceptada {
+            match accion.tipo {
+                TipoModeracion::Ocultar => {
+                    marcar(&env, &DataKey::SaludoOculto(accion.usuario.clone()), false)
+                }
+                TipoModeracion::Suspender => {
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::Suspendido(accion.usuario.clone()))
+                }
+                TipoModeracion::Borrar => {}
+            }
+            accion.apelacion = EstadoApelacion::Aceptada;
+        } else {
+            accion.apelacion = EstadoApelacion::Rechazada;
+        }
+
+        guardar_moderacion(&env, &accion);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// MÓDULO DE TESTS
+// ============================================================================
+// #[cfg(test)] = este código solo se compila en modo test
+// Permite verificar que el contrato funciona correctamente antes de deploy
+// ============================================================================
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    // Importar todo del módulo padre (el contrato)
+    use super::*;
+    // Importar utilidades de testing de Soroban
+    use soroban_sdk::{
+        Env, Event,
+        testutils::{
+            Address as TestAddress, Events as _, Ledger as _, MockAuth, MockAuthInvoke,
+            storage::Persistent as _,
+        },
+    };
+
+    // Argumentos del constructor para el despliegue en dos pasos:
+    // el contrato queda sin admin hasta que el test llama initialize()
+    const SIN_ADMIN: (Option<Address>,) = (None,);
+
+    // ========================================================================
+    // TEST: Inicialización básica
+    // ========================================================================
+    // Verifica que el contrato se pueda inicializar correctamente
+    // ========================================================================
+    #[test]
+    fn test_initialize() {
+        // ARRANGE: Preparar el ambiente de test
+        // --------------------------------------
+        // Env::default() crea un ambiente simulado de blockchain
+        let env = Env::default();
+
+        // Registrar el contrato en el ambiente de test
+        // .register() retorna el ID del contrato
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+
+        // Crear un cliente para interactuar con el contrato
+        // El SDK genera HelloContractClient automáticamente
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Generar una dirección aleatoria para admin
+        let admin = Address::generate(&env);
+
+        // ACT: Ejecutar la acción a testear
+        // ----------------------------------
+        client.initialize(&admin);
+
+        // ASSERT: Verificar el resultado esperado
+        // ----------------------------------------
+        // El contador debe estar en 0 después de inicializar
+        assert_eq!(client.get_contador(), 0);
+        // El límite arranca en 32 caracteres
+        assert_eq!(client.get_limite_nombre(), 32);
+    }
+
+    // ========================================================================
+    // TEST: No permitir re-inicialización
+    // ========================================================================
+    // Verifica que el contrato no se pueda inicializar dos veces
+    // #[should_panic] = esperamos que este test cause panic
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Contract, #49)")]  // Error #49 = YaInicializado
+    fn test_no_reinicializar() {
+        // ARRANGE
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        // ACT
+        client.initialize(&admin);      // Primera inicialización - OK
+        client.initialize(&admin);      // Segunda inicialización - DEBE FALLAR
+
+        // ASSERT: El test PASA si hay panic con el mensaje esperado
+    }
+
+    // ========================================================================
+    // TEST: Función hello exitosa
+    // ========================================================================
+    // Verifica que hello() funcione correctamente con inputs válidos
+    // ========================================================================
+    #[test]
+    fn test_hello_exitoso() {
+        // ARRANGE
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Generar dos direcciones: admin y usuario normal
+        let admin = Address::generate(&env);
+        let usuario = Address::generate(&env);
+
+        // Inicializar el contrato primero
+        client.initialize(&admin);
+
+        // ACT
+        // Crear un String en el ambiente de Soroban
+        let nombre = String::from_str(&env, "Ana");
+        let resultado = client.hello(&usuario, &nombre);
+
+        // ASSERT: Verificar múltiples condiciones
+        // ----------------------------------------
+        // 1. La función retorna "Hola"
+        assert_eq!(resultado, String::from_str(&env, "Hola"));
+
+        // 2. El contador se incrementó a 1
+        assert_eq!(client.get_contador(), 1);
+
+        // 3. El saludo del usuario se guardó correctamente
+        assert_eq!(client.get_ultimo_saludo(&usuario), Some(nombre));
+    }
+
+    // ========================================================================
+    // TEST: Validación de nombre vacío
+    // ========================================================================
+    // Verifica que hello() rechace nombres vacíos
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]  // Error #1 = NombreVacio
+    fn test_nombre_vacio() {
+        // ARRANGE
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usuario = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // ACT
+        // Crear un String vacío
+        let vacio = String::from_str(&env, "");
+        client.hello(&usuario, &vacio);  // DEBE FALLAR con Error #1
+
+        // ASSERT: El test PASA si hay panic con Error #1
+    }
+
+    // ========================================================================
+    // TEST: Solo admin puede resetear
+    // ========================================================================
+    // Verifica que el admin pueda resetear el contador
+    // ========================================================================
+    #[test]
+    fn test_reset_solo_admin() {
+        // ARRANGE
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usuario = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // ACT
+        // 1. Hacer un saludo (contador = 1)
+        client.hello(&usuario, &String::from_str(&env, "Test"));
+        assert_eq!(client.get_contador(), 1);
+
+        // 2. Admin resetea el contador
+        client.reset_contador(&admin);
+
+        // ASSERT
+        // El contador debe estar en 0 después del reset
+        assert_eq!(client.get_contador(), 0);
+    }
+
+    // ========================================================================
+    // TEST: No admin no puede resetear
+    // ========================================================================
+    // Verifica que usuarios NO admin no puedan resetear el contador
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]  // Error #3 = NoAutorizado
+    fn test_reset_no_autorizado() {
+        // ARRANGE
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Generar dos direcciones diferentes
+        let admin = Address::generate(&env);
+        let otro = Address::generate(&env);   // Usuario NO admin
+
+        client.initialize(&admin);
+
+        // ACT
+        // Un usuario NO admin intenta resetear
+        client.reset_contador(&otro);  // DEBE FALLAR con Error #3
+
+        // ASSERT: El test PASA si hay panic con Error #3
+    }
+
+    // ========================================================================
+    // TEST: Contador individual por usuario
+    // ========================================================================
+    #[test]
+    fn test_contador_por_usuario() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let maria = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // María saluda 3 veces
+        client.hello(&maria, &String::from_str(&env, "Maria1"));
+        client.hello(&maria, &String::from_str(&env, "Maria2"));
+        client.hello(&maria, &String::from_str(&env, "Maria3"));
+
+        // Ana saluda 1 vez
+        client.hello(&ana, &String::from_str(&env, "Ana1"));
+
+        // Verificar contadores independientes
+        assert_eq!(client.get_contador_usuario(&maria), 3);  // María: 3
+        assert_eq!(client.get_contador_usuario(&ana), 1);    // Ana: 1
+        assert_eq!(client.get_contador(), 4);                 // Total: 4
+    }
+
+    // ========================================================================
+    // TEST: Transferencia de admin
+    // ========================================================================
+    // El nuevo admin gana permisos y el viejo los pierde
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_transfer_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin1 = Address::generate(&env);
+        let admin2 = Address::generate(&env);
+
+        client.initialize(&admin1);
+        client.transfer_admin(&admin1, &admin2);
+        assert_eq!(client.get_admin(), admin2.clone());
+
+        // El nuevo admin puede resetear
+        client.reset_contador(&admin2);
+
+        // El admin viejo NO puede resetear
+        client.reset_contador(&admin1);
+    }
+
+    // ========================================================================
+    // TEST: Límite configurable
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_limite_se_respeta() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usuario = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // Cambiar límite a 5 caracteres
+        client.set_limite_nombre(&admin, &5);
+        assert_eq!(client.get_limite_nombre(), 5);
+
+        // Nombre de 5 caracteres funciona
+        client.hello(&usuario, &String::from_str(&env, "Sofia"));
+
+        // Nombre de 7 caracteres DEBE FALLAR
+        client.hello(&usuario, &String::from_str(&env, "NombreL"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #5)")]
+    fn test_limite_invalido() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // 300 > 256 → LimiteInvalido
+        client.set_limite_nombre(&admin, &300);
+    }
+
+    // ========================================================================
+    // TEST: Salas independientes
+    // ========================================================================
+    // Cada sala lleva su propio contador, último saludo y contador por usuario
+    // La sala general (hello legacy) no se mezcla con las demás
+    // ========================================================================
+    #[test]
+    fn test_salas_independientes() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let admin_meetup = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let meetup = symbol_short!("meetup");
+
+        client.initialize(&admin);
+        client.crear_sala(&admin, &meetup, &admin_meetup, &10);
+        assert_eq!(client.get_admin_sala(&meetup), Some(admin_meetup.clone()));
+        assert_eq!(client.get_limite_sala(&meetup), 10);
+
+        // Ana saluda dos veces en la sala y una en la general
+        let en_sala = String::from_str(&env, "Ana Meetup");
+        client.hello_en_sala(&meetup, &ana, &String::from_str(&env, "Ana"));
+        client.hello_en_sala(&meetup, &ana, &en_sala);
+        client.hello(&ana, &String::from_str(&env, "Ana General"));
+
+        assert_eq!(client.get_contador_sala(&meetup), 2);
+        assert_eq!(client.get_contador_usuario_sala(&meetup, &ana), 2);
+        assert_eq!(client.get_ultimo_saludo_sala(&meetup, &ana), Some(en_sala));
+
+        // La sala general se consulta igual con los getters legacy o por sala
+        assert_eq!(client.get_contador(), 1);
+        assert_eq!(client.get_contador_sala(&SALA_GENERAL), 1);
+        assert_eq!(
+            client.get_ultimo_saludo_sala(&SALA_GENERAL, &ana),
+            client.get_ultimo_saludo(&ana)
+        );
+
+        // Resetear la sala no toca la general
+        client.reset_contador_sala(&meetup, &admin_meetup);
+        assert_eq!(client.get_contador_sala(&meetup), 0);
+        assert_eq!(client.get_contador(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #7)")]  // Error #7 = SalaNoExiste
+    fn test_hello_sala_inexistente() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let usuario = Address::generate(&env);
+
+        client.hello_en_sala(&symbol_short!("fantasma"), &usuario, &String::from_str(&env, "Ana"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #6)")]  // Error #6 = SalaYaExiste
+    fn test_crear_sala_duplicada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let otro = Address::generate(&env);
+        let meetup = symbol_short!("meetup");
+
+        client.crear_sala(&admin, &meetup, &admin, &32);
+        client.crear_sala(&admin, &meetup, &otro, &32);  // DEBE FALLAR con Error #6
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #6)")]
+    fn test_crear_sala_general() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // La sala general está reservada para hello()
+        client.crear_sala(&admin, &SALA_GENERAL, &admin, &32);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_limite_por_sala() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let usuario = Address::generate(&env);
+        let corta = symbol_short!("corta");
+
+        client.initialize(&admin);
+        client.crear_sala(&admin, &corta, &admin, &3);
+
+        // El nombre es válido en la general (límite 32)...
+        let nombre = String::from_str(&env, "Sofia");
+        client.hello(&usuario, &nombre);
+
+        // ...pero no en una sala con límite 3
+        client.hello_en_sala(&corta, &usuario, &nombre);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_admin_general_no_administra_salas() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let admin_meetup = Address::generate(&env);
+        let meetup = symbol_short!("meetup");
+
+        client.initialize(&admin);
+        client.crear_sala(&admin, &meetup, &admin_meetup, &32);
+
+        // Cada sala tiene su propio admin
+        client.set_limite_sala(&meetup, &admin, &10);
+    }
+
+    #[test]
+    fn test_salas_solo_admin_general_y_auditadas() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin_meetup = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let meetup = symbol_short!("meetup");
+
+        // Nadie más puede crear (ni reservar) un id de sala
+        assert_eq!(
+            client.try_crear_sala(&ana, &meetup, &ana, &32),
+            Err(Ok(Error::NoAutorizado))
+        );
+        assert_eq!(client.get_admin_sala(&meetup), None);
+
+        // Firman el admin general y el de la sala
+        client.crear_sala(&admin, &meetup, &admin_meetup, &32);
+        let firmas = Vec::from_iter(&env, env.auths().into_iter().map(|(firmante, _)| firmante));
+        assert_eq!(firmas, vec![&env, admin.clone(), admin_meetup.clone()]);
+
+        client.hello_en_sala(&meetup, &ana, &String::from_str(&env, "Ana"));
+        client.set_limite_sala(&meetup, &admin_meetup, &10);
+        client.reset_contador_sala(&meetup, &admin_meetup);
+
+        // Cada acción queda en la auditoría con la sala
+        let entradas = client.get_auditorias(&0, &10);
+        assert_eq!(entradas.len(), 3);
+        let resumen = Vec::from_iter(&env, entradas.iter().map(|e| {
+            assert_eq!(e.sala, Some(meetup.clone()));
+            assert_eq!(e.usuario, None);
+            (e.actor, e.accion, e.anterior, e.nuevo)
+        }));
+        assert_eq!(
+            resumen,
+            vec![
+                &env,
+                (
+                    admin.clone(),
+                    AccionAuditoria::CrearSala,
+                    ValorAuditoria::Ninguno,
+                    ValorAuditoria::Direccion(admin_meetup.clone()),
+                ),
+                (
+                    admin_meetup.clone(),
+                    AccionAuditoria::SetLimiteSala,
+                    ValorAuditoria::Numero(32),
+                    ValorAuditoria::Numero(10),
+                ),
+                (
+                    admin_meetup.clone(),
+                    AccionAuditoria::ResetContadorSala,
+                    ValorAuditoria::Numero(1),
+                    ValorAuditoria::Numero(0),
+                ),
+            ]
+        );
+    }
+
+    // ========================================================================
+    // CONTRATOS HOOK DE PRUEBA
+    // ========================================================================
+    // HookRegistro guarda lo que recibe; HookRoto siempre falla
+    // ========================================================================
+    #[contract]
+    pub struct HookRegistro;
+
+    #[contractimpl]
+    impl SaludoHook for HookRegistro {
+        fn on_hello(env: Env, usuario: Address, nombre: String, contador: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ultimo"), &(usuario, nombre, contador));
+        }
+    }
+
+    #[contract]
+    pub struct HookRoto;
+
+    #[contractimpl]
+    impl SaludoHook for HookRoto {
+        fn on_hello(_env: Env, _usuario: Address, _nombre: String, _contador: u64) {
+            panic!("hook roto");
+        }
+    }
+
+    #[test]
+    fn test_hook_recibe_saludo() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+        let hook_id = env.register(HookRegistro, ());
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.registrar_hook(&admin, &hook_id, &true);
+
+        let nombre = String::from_str(&env, "Ana");
+        client.hello(&ana, &nombre);
+        client.hello(&ana, &nombre);
+
+        // El hook vio el contador ya actualizado
+        let recibido: (Address, String, u64) = env.as_contract(&hook_id, || {
+            env.storage().instance().get(&symbol_short!("ultimo")).unwrap()
+        });
+        assert_eq!(recibido, (ana, nombre, 2));
+    }
+
+    #[test]
+    fn test_hook_obligatorio_aborta_saludo() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+        let hook_id = env.register(HookRoto, ());
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.registrar_hook(&admin, &hook_id, &true);
+
+        let resultado = client.try_hello(&ana, &String::from_str(&env, "Ana"));
+        assert_eq!(resultado, Err(Ok(Error::HookFallido)));
+
+        // El Err revierte el estado: el saludo no quedó registrado
+        assert_eq!(client.get_contador(), 0);
+        assert_eq!(client.get_ultimo_saludo(&ana), None);
+    }
+
+    #[test]
+    fn test_hook_opcional_emite_evento() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+        let hook_id = env.register(HookRoto, ());
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.registrar_hook(&admin, &hook_id, &false);
+
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+
+        // events().all() solo ve los eventos de la última invocación
+        let evento = HookFallido { hook: hook_id, usuario: ana, contador: 1 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id, evento.topics(&env), evento.data(&env))]
+        );
+
+        // El fallo se ignoró: el saludo sí quedó registrado
+        assert_eq!(client.get_contador(), 1);
+    }
+
+    #[test]
+    fn test_registrar_y_quitar_hooks() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+        let hook_id = env.register(HookRegistro, ());
+
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.registrar_hook(&admin, &hook_id, &true);
+
+        // Registrar de nuevo solo cambia la configuración
+        client.registrar_hook(&admin, &hook_id, &false);
+        assert_eq!(
+            client.get_hooks(),
+            vec![&env, Hook { contrato: hook_id.clone(), obligatorio: false }]
+        );
+
+        client.quitar_hook(&admin, &hook_id);
+        assert_eq!(client.get_hooks().len(), 0);
+        assert_eq!(client.try_quitar_hook(&admin, &hook_id), Err(Ok(Error::HookNoExiste)));
+    }
+
+    #[test]
+    fn test_hooks_tope() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let primero = Address::generate(&env);
+        client.registrar_hook(&admin, &primero, &false);
+        for _ in 1..MAX_HOOKS {
+            client.registrar_hook(&admin, &Address::generate(&env), &false);
+        }
+
+        assert_eq!(
+            client.try_registrar_hook(&admin, &Address::generate(&env), &false),
+            Err(Ok(Error::DemasiadosHooks))
+        );
+
+        // Con la lista llena todavía se puede reconfigurar uno existente
+        client.registrar_hook(&admin, &primero, &true);
+        assert_eq!(client.get_hooks().len(), MAX_HOOKS);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_registrar_hook_no_autorizado() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+        let hook_id = env.register(HookRegistro, ());
+
+        let admin = Address::generate(&env);
+        let otro = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.registrar_hook(&otro, &hook_id, &true);
+    }
+
+    // ========================================================================
+    // TEST: Lista de permitidos y bloqueados
+    // ========================================================================
+    #[test]
+    fn test_modo_lista_permitidos() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let moderador = Address::generate(&env);
+        let asistente = Address::generate(&env);
+        let colada = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.initialize(&admin);
+        client.agregar_moderador(&admin, &moderador);
+
+        // Sin modo lista todos pueden saludar
+        assert!(client.esta_permitido(&colada));
+
+        // El moderador activa el modo lista y registra asistentes
+        client.set_modo_lista(&moderador, &true);
+        client.agregar_permitidos(&moderador, &vec![&env, asistente.clone()]);
+
+        assert!(client.esta_permitido(&asistente));
+        assert!(!client.esta_permitido(&colada));
+
+        client.hello(&asistente, &nombre);
+        assert_eq!(client.try_hello(&colada, &nombre), Err(Ok(Error::NoEstaEnLista)));
+
+        // Quitar de la lista revoca el acceso
+        client.quitar_permitidos(&moderador, &vec![&env, asistente.clone()]);
+        assert!(!client.esta_permitido(&asistente));
+    }
+
+    // ========================================================================
+    // TEST: Saludar a nombre de otra dirección
+    // ========================================================================
+    #[test]
+    fn test_saludo_a_nombre_de_otro() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let asistente = Address::generate(&env);
+        let colada = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+
+        // La colada firma su transacción pero pasa la dirección del asistente
+        env.mock_auths(&[MockAuth {
+            address: &colada,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "hello",
+                args: (&asistente, &nombre).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(client.try_hello(&asistente, &nombre).is_err());
+        assert_eq!(client.get_contador(), 0);
+        assert_eq!(client.get_ultimo_saludo(&asistente), None);
+    }
+
+    #[test]
+    fn test_variantes_de_hello_piden_firma() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+        let meetup = Symbol::new(&env, "meetup");
+        let id = Symbol::new(&env, "simple");
+        client.crear_sala(&admin, &meetup, &admin, &32);
+        client.set_plantilla(&admin, &id, &String::from_str(&env, "Hola {nombre}"));
+        client.set_perfil(&ana, &nombre, &String::from_str(&env, ""), &None, &None);
+
+        // Cada variante registra la firma de `usuario` y de nadie más
+        let firmo_solo_ana = |env: &Env| {
+            let firmas = env.auths();
+            firmas.len() == 1 && firmas[0].0 == ana
+        };
+        client.hello(&ana, &nombre);
+        assert!(firmo_solo_ana(&env));
+        client.hello_en_sala(&meetup, &ana, &nombre);
+        assert!(firmo_solo_ana(&env));
+        client.hello_perfil(&ana);
+        assert!(firmo_solo_ana(&env));
+        client.hello_plantilla(&ana, &nombre, &id);
+        assert!(firmo_solo_ana(&env));
+        client.hello_efimero(&ana, &nombre, &10);
+        assert!(firmo_solo_ana(&env));
+    }
+
+    #[test]
+    fn test_bloqueo_gana_sobre_lista() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let spammer = Address::generate(&env);
+        let nombre = String::from_str(&env, "Spam");
+
+        client.initialize(&admin);
+        client.agregar_permitidos(&admin, &vec![&env, spammer.clone()]);
+        client.bloquear(&admin, &vec![&env, spammer.clone()]);
+
+        assert!(!client.esta_permitido(&spammer));
+        assert_eq!(client.try_hello(&spammer, &nombre), Err(Ok(Error::UsuarioBloqueado)));
+
+        client.desbloquear(&admin, &vec![&env, spammer.clone()]);
+        client.hello(&spammer, &nombre);
+        assert_eq!(client.get_contador(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
+    fn test_bloquear_no_autorizado() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let otro = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.bloquear(&otro, &vec![&env, admin.clone()]);
+    }
+
+    // ========================================================================
+    // TEST: Pasar la dirección del admin o de un moderador sin su firma
+    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_admin_sin_firma() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Nadie firma: la dirección del admin como argumento no alcanza
+        client.reset_contador(&admin);
+    }
+
+    #[test]
+    fn test_admin_firma_de_otro() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let atacante = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let invocacion = MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "set_modo_desbordamiento",
+            args: (&admin, ModoDesbordamiento::Saturar).into_val(&env),
+            sub_invokes: &[],
+        };
+
+        // El atacante firma su transacción pero pasa la dirección del admin
+        env.mock_auths(&[MockAuth { address: &atacante, invoke: &invocacion }]);
+        assert!(client.try_set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar).is_err());
+        assert_eq!(client.get_modo_desbordamiento(), ModoDesbordamiento::Fallar);
+
+        // Con la firma del admin sí
+        env.mock_auths(&[MockAuth { address: &admin, invoke: &invocacion }]);
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar);
+        assert_eq!(client.get_modo_desbordamiento(), ModoDesbordamiento::Saturar);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_moderador_sin_firma() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let moderador = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        env.mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "agregar_moderador",
+                args: (&admin, &moderador).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.agregar_moderador(&admin, &moderador);
+
+        // Sin la firma del moderador
+        client.bloquear(&moderador, &vec![&env, ana]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #13)")]  // Error #13 = LoteMuyGrande
+    fn test_lote_muy_grande() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        let mut usuarios = Vec::new(&env);
+        for _ in 0..=MAX_LOTE {
+            usuarios.push_back(Address::generate(&env));
+        }
+        client.agregar_permitidos(&admin, &usuarios);
+    }
+
+    // ========================================================================
+    // TEST: Lista Merkle
+    // ========================================================================
+    // arbol_merkle::ArbolMerkle arma el árbol del lado del host (con sha2)
+    // para comprobar que el contrato hashea igual
+    // ========================================================================
+    #[test]
+    fn test_hello_con_prueba() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let asistentes: std::vec::Vec<Address> =
+            (0..5).map(|_| Address::generate(&env)).collect();
+        let arbol = arbol_merkle::ArbolMerkle::nuevo(&env, &asistentes);
+
+        client.initialize(&admin);
+        client.set_raiz_merkle(&admin, &arbol.raiz(&env));
+
+        // Con modo lista activo, la prueba reemplaza a la lista por dirección
+        client.set_modo_lista(&admin, &true);
+
+        for (i, asistente) in asistentes.iter().enumerate() {
+            let nombre = String::from_str(&env, "Ana");
+            client.hello_con_prueba(asistente, &nombre, &arbol.prueba(&env, i));
+        }
+        assert_eq!(client.get_contador(), 5);
+    }
+
+    #[test]
+    fn test_prueba_invalida_y_hoja_usada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let colada = Address::generate(&env);
+        let asistentes: std::vec::Vec<Address> =
+            (0..4).map(|_| Address::generate(&env)).collect();
+        let arbol = arbol_merkle::ArbolMerkle::nuevo(&env, &asistentes);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.initialize(&admin);
+        assert_eq!(
+            client.try_hello_con_prueba(&asistentes[0], &nombre, &arbol.prueba(&env, 0)),
+            Err(Ok(Error::RaizNoConfigurada))
+        );
+
+        client.set_raiz_merkle(&admin, &arbol.raiz(&env));
+
+        // Una dirección fuera del árbol no puede usar la prueba de otra
+        assert_eq!(
+            client.try_hello_con_prueba(&colada, &nombre, &arbol.prueba(&env, 0)),
+            Err(Ok(Error::PruebaInvalida))
+        );
+
+        // Cada hoja se puede usar una sola vez por raíz
+        client.hello_con_prueba(&asistentes[0], &nombre, &arbol.prueba(&env, 0));
+        assert_eq!(
+            client.try_hello_con_prueba(&asistentes[0], &nombre, &arbol.prueba(&env, 0)),
+            Err(Ok(Error::HojaYaUsada))
+        );
+    }
+
+    // ========================================================================
+    // TEST: Saludos firmados off-chain
+    // ========================================================================
+    // La clave ed25519 se genera con ed25519-dalek a partir de una semilla fija
+    // ========================================================================
+    fn firmar(
+        env: &Env,
+        client: &HelloContractClient,
+        clave: &ed25519_dalek::SigningKey,
+        nombre: &String,
+        nonce: u64,
+        expiracion: u32
+    ) -> (BytesN<32>, BytesN<64>) {
+        use ed25519_dalek::Signer;
+
+        let pubkey = BytesN::from_array(env, &clave.verifying_key().to_bytes());
+        let payload = client.get_payload_firma(&pubkey, nombre, &nonce, &expiracion);
+        let mensaje: std::vec::Vec<u8> = payload.iter().collect();
+        let firma = BytesN::from_array(env, &clave.sign(&mensaje).to_bytes());
+        (pubkey, firma)
+    }
+
+    #[test]
+    fn test_hello_firmado() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let clave = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.initialize(&admin);
+
+        let (pubkey, firma) = firmar(&env, &client, &clave, &nombre, 0, 100);
+        client.hello_firmado(&pubkey, &nombre, &0, &100, &firma);
+
+        // El saludo queda a nombre de la cuenta G... del firmante
+        let strkey = stellar_strkey::ed25519::PublicKey(clave.verifying_key().to_bytes());
+        let firmante = Address::from_str(&env, &strkey.to_string());
+        assert_eq!(client.get_ultimo_saludo(&firmante), Some(nombre));
+        assert_eq!(client.get_nonce_firma(&pubkey), 1);
+
+        // Repetir la misma firma falla: el nonce ya se consumió
+        assert_eq!(
+            client.try_hello_firmado(&pubkey, &String::from_str(&env, "Ana"), &0, &100, &firma),
+            Err(Ok(Error::NonceInvalido))
+        );
+    }
+
+    #[test]
+    fn test_hello_firmado_expirado() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let clave = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let nombre = String::from_str(&env, "Ana");
+
+        let (pubkey, firma) = firmar(&env, &client, &clave, &nombre, 0, 10);
+        env.ledger().set_sequence_number(11);
+
+        assert_eq!(
+            client.try_hello_firmado(&pubkey, &nombre, &0, &10, &firma),
+            Err(Ok(Error::FirmaExpirada))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Crypto, InvalidInput)")]
+    fn test_hello_firmado_nombre_alterado() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let clave = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let nombre = String::from_str(&env, "Ana");
+
+        // El relayer cambia el nombre: la firma ya no corresponde
+        let (pubkey, firma) = firmar(&env, &client, &clave, &nombre, 0, 100);
+        client.hello_firmado(&pubkey, &String::from_str(&env, "Eva"), &0, &100, &firma);
+    }
+
+    // ========================================================================
+    // TEST: Passkeys secp256r1
+    // ========================================================================
+    // Los vectores de prueba se generan con p256 a partir de un escalar fijo:
+    // la firma ECDSA es determinística (RFC 6979), así que los tests también
+    // ========================================================================
+    fn passkey(env: &Env, semilla: u8) -> (p256::ecdsa::SigningKey, BytesN<65>) {
+        let clave = p256::ecdsa::SigningKey::from_bytes(&[semilla; 32].into()).unwrap();
+        let punto = clave.verifying_key().to_encoded_point(false);
+        let pubkey = BytesN::from_array(env, punto.as_bytes().try_into().unwrap());
+        (clave, pubkey)
+    }
+
+    // Aserción WebAuthn como la arma el navegador: authenticatorData con
+    // rpIdHash + flags UP|UV + signCount, y clientDataJSON con el challenge
+    // en base64url
+    struct Asercion {
+        authenticator_data: Bytes,
+        client_data_json: Bytes,
+        firma: BytesN<64>,
+    }
+
+    fn datos_autenticador(flags: u8) -> std::vec::Vec<u8> {
+        use sha2::{Digest, Sha256};
+
+        let mut datos = Sha256::digest(b"localhost").to_vec();
+        datos.push(flags);
+        datos.extend_from_slice(&7u32.to_be_bytes());
+        datos
+    }
+
+    fn client_data(tipo: &str, challenge: &[u8]) -> std::string::String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+        std::format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"http://localhost:5173","crossOrigin":false}}"#,
+            tipo,
+            URL_SAFE_NO_PAD.encode(challenge)
+        )
+    }
+
+    fn firmar_webauthn(
+        env: &Env,
+        clave: &p256::ecdsa::SigningKey,
+        authenticator_data: &[u8],
+        client_data_json: &str
+    ) -> Asercion {
+        use p256::ecdsa::{signature::Signer, Signature};
+        use sha2::{Digest, Sha256};
+
+        let mut mensaje = authenticator_data.to_vec();
+        mensaje.extend_from_slice(&Sha256::digest(client_data_json.as_bytes()));
+        // sign() hashea con sha256, igual que el contrato
+        let firma: Signature = clave.sign(&mensaje);
+        // El host exige s "bajo"; normalize_s() retorna None si ya lo es
+        let firma = firma.normalize_s().unwrap_or(firma);
+        Asercion {
+            authenticator_data: Bytes::from_slice(env, authenticator_data),
+            client_data_json: Bytes::from_slice(env, client_data_json.as_bytes()),
+            firma: BytesN::from_array(env, &firma.to_bytes().into()),
+        }
+    }
+
+    fn firmar_passkey(
+        env: &Env,
+        client: &HelloContractClient,
+        clave: &p256::ecdsa::SigningKey,
+        nombre: &String,
+        nonce: u64
+    ) -> Asercion {
+        let challenge = client.get_challenge_passkey(nombre, &nonce).to_array();
+        firmar_webauthn(env, clave, &datos_autenticador(0x05), &client_data("webauthn.get", &challenge))
+    }
+
+    fn hello_passkey(
+        client: &HelloContractClient,
+        pubkey: &BytesN<65>,
+        nombre: &String,
+        nonce: u64,
+        asercion: &Asercion
+    ) -> Result<Result<String, soroban_sdk::ConversionError>, Result<soroban_sdk::Error, InvokeError>> {
+        client.try_hello_passkey(
+            pubkey,
+            nombre,
+            &nonce,
+            &asercion.authenticator_data,
+            &asercion.client_data_json,
+            &asercion.firma,
+        )
+    }
+
+    #[test]
+    fn test_base64url_del_challenge() {
+        // sha256("") en base64url, el ejemplo de la spec de WebAuthn
+        use sha2::{Digest, Sha256};
+
+        let digest: [u8; 32] = Sha256::digest(b"").into();
+        assert_eq!(&base64url_32(&digest), b"47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU");
+        assert_eq!(&base64url_32(&[0xff; 32]), b"__________________________________________8");
+    }
+
+    #[test]
+    fn test_hello_passkey() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let (clave, pubkey) = passkey(&env, 3);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.initialize(&admin);
+        client.registrar_passkey(&ana, &pubkey);
+        assert_eq!(client.get_usuario_passkey(&pubkey), Some(ana.clone()));
+
+        let asercion = firmar_passkey(&env, &client, &clave, &nombre, 0);
+        assert!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion).is_ok());
+
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(nombre.clone()));
+        assert_eq!(client.get_nonce_passkey(&pubkey), 1);
+
+        // La misma firma no se puede repetir
+        assert_eq!(
+            hello_passkey(&client, &pubkey, &nombre, 0, &asercion),
+            Err(Ok(Error::NonceInvalido.into()))
+        );
+    }
+
+    #[test]
+    fn test_hello_passkey_datos_webauthn() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let (clave, pubkey) = passkey(&env, 3);
+        let nombre = String::from_str(&env, "Ana");
+        client.registrar_passkey(&ana, &pubkey);
+
+        let challenge = client.get_challenge_passkey(&nombre, &0).to_array();
+        let invalida = Err(Ok(ErrorExtra::DatosWebAuthnInvalidos.into()));
+
+        // Firma válida, pero sobre el challenge de otro nonce
+        let otro = client.get_challenge_passkey(&nombre, &1).to_array();
+        let asercion = firmar_webauthn(
+            &env, &clave, &datos_autenticador(0x05), &client_data("webauthn.get", &otro)
+        );
+        assert_eq!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion), invalida);
+
+        // Una aserción de registro no sirve para saludar
+        let asercion = firmar_webauthn(
+            &env, &clave, &datos_autenticador(0x05), &client_data("webauthn.create", &challenge)
+        );
+        assert_eq!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion), invalida);
+
+        // Sin la bandera UP no hubo gesto del usuario
+        let asercion = firmar_webauthn(
+            &env, &clave, &datos_autenticador(0x04), &client_data("webauthn.get", &challenge)
+        );
+        assert_eq!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion), invalida);
+
+        // authenticatorData truncado
+        let asercion = firmar_webauthn(
+            &env, &clave, &datos_autenticador(0x05)[..32], &client_data("webauthn.get", &challenge)
+        );
+        assert_eq!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion), invalida);
+
+        // clientDataJSON más largo que MAX_CLIENT_DATA_JSON
+        let largo = std::format!(
+            "{}{}",
+            client_data("webauthn.get", &challenge),
+            " ".repeat(MAX_CLIENT_DATA_JSON as usize)
+        );
+        let asercion = firmar_webauthn(&env, &clave, &datos_autenticador(0x05), &largo);
+        assert_eq!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion), invalida);
+
+        // Ningún intento fallido consume el nonce
+        assert_eq!(client.get_nonce_passkey(&pubkey), 0);
+        let asercion = firmar_passkey(&env, &client, &clave, &nombre, 0);
+        assert!(hello_passkey(&client, &pubkey, &nombre, 0, &asercion).is_ok());
+    }
+
+    #[test]
+    fn test_passkey_registro() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        let (clave, pubkey) = passkey(&env, 3);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.registrar_passkey(&ana, &pubkey);
+
+        // Una passkey pertenece a un solo usuario
+        assert_eq!(
+            client.try_registrar_passkey(&eva, &pubkey),
+            Err(Ok(Error::PasskeyYaRegistrada))
+        );
+        assert_eq!(client.try_quitar_passkey(&eva, &pubkey), Err(Ok(Error::NoAutorizado)));
+
+        // Sin vínculo, la passkey no puede saludar
+        client.quitar_passkey(&ana, &pubkey);
+        let asercion = firmar_passkey(&env, &client, &clave, &nombre, 0);
+        assert_eq!(
+            hello_passkey(&client, &pubkey, &nombre, 0, &asercion),
+            Err(Ok(Error::PasskeyNoRegistrada.into()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Crypto, InvalidInput)")]
+    fn test_hello_passkey_firma_de_otra_clave() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let (_, pubkey) = passkey(&env, 3);
+        let (otra_clave, _) = passkey(&env, 4);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.registrar_passkey(&ana, &pubkey);
+
+        let asercion = firmar_passkey(&env, &client, &otra_clave, &nombre, 0);
+        client.hello_passkey(
+            &pubkey,
+            &nombre,
+            &0,
+            &asercion.authenticator_data,
+            &asercion.client_data_json,
+            &asercion.firma,
+        );
+    }
+
+    // ========================================================================
+    // TEST: Consejo multisig
+    // ========================================================================
+    fn consejo_2_de_3(env: &Env, client: &HelloContractClient) -> (Address, Address, Address) {
+        let admin = Address::generate(env);
+        let (a, b, c) = (Address::generate(env), Address::generate(env), Address::generate(env));
+
+        client.initialize(&admin);
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![env, a.clone(), b.clone(), c.clone()], umbral: 2 },
+        );
+
+        (a, b, c)
+    }
+
+    #[test]
+    fn test_consejo_ejecuta_con_umbral() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let (a, b, _) = consejo_2_de_3(&env, &client);
+
+        let id = client.proponer(&a, &AccionConsejo::SetLimiteNombre(10), &100);
+
+        // Con 1 de 2 aprobaciones la propuesta sigue pendiente
+        assert_eq!(client.get_limite_nombre(), 32);
+        assert_eq!(client.get_propuestas_pendientes().len(), 1);
+        assert_eq!(client.try_aprobar(&a, &id), Err(Ok(Error::YaAprobada)));
+
+        // La segunda aprobación la ejecuta
+        assert!(client.aprobar(&b, &id));
+        assert_eq!(client.get_limite_nombre(), 10);
+        assert_eq!(client.get_propuesta(&id).unwrap().estado, EstadoPropuesta::Ejecutada);
+        assert_eq!(client.get_propuestas_pendientes().len(), 0);
+    }
+
+    #[test]
+    fn test_consejo_bloquea_admin_unico() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let firmante = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, firmante.clone()], umbral: 1 },
+        );
+
+        assert_eq!(client.try_reset_contador(&admin), Err(Ok(Error::RequiereConsejo)));
+        assert_eq!(client.try_set_limite_nombre(&admin, &10), Err(Ok(Error::RequiereConsejo)));
+
+        // Umbral 1: proponer ejecuta de inmediato
+        client.hello(&firmante, &String::from_str(&env, "Ana"));
+        client.proponer(&firmante, &AccionConsejo::ResetContador, &10);
+        assert_eq!(client.get_contador(), 0);
+    }
+
+    #[test]
+    fn test_consejo_solo_lo_configura_el_admin() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        let atacante = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let consejo = Consejo { firmantes: vec![&env, atacante.clone()], umbral: 1 };
+        let firma_de = |firmante: &Address, caller: &Address| {
+            env.mock_auths(&[MockAuth {
+                address: firmante,
+                invoke: &MockAuthInvoke {
+                    contract: &contract_id,
+                    fn_name: "configurar_consejo",
+                    args: (caller, &consejo).into_val(&env),
+                    sub_invokes: &[],
+                },
+            }]);
+        };
+
+        // Con su propia dirección: no es el admin
+        firma_de(&atacante, &atacante);
+        assert_eq!(
+            client.try_configurar_consejo(&atacante, &consejo),
+            Err(Ok(Error::NoAutorizado))
+        );
+
+        // Con la dirección del admin: falta la firma del admin
+        firma_de(&atacante, &admin);
+        assert!(client.try_configurar_consejo(&admin, &consejo).is_err());
+
+        // El admin sigue a cargo
+        assert_eq!(client.get_consejo(), None);
+        env.mock_all_auths();
+        client.reset_contador(&admin);
+    }
+
+    #[test]
+    fn test_propuesta_expirada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let (a, b, _) = consejo_2_de_3(&env, &client);
+
+        let id = client.proponer(&a, &AccionConsejo::ResetContador, &10);
+        env.ledger().set_sequence_number(11);
+
+        assert_eq!(client.try_aprobar(&b, &id), Err(Ok(Error::PropuestaExpirada)));
+        assert_eq!(client.get_propuestas_pendientes().len(), 0);
+    }
+
+    #[test]
+    fn test_cambiar_consejo_por_propuesta() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let (a, b, c) = consejo_2_de_3(&env, &client);
+        let d = Address::generate(&env);
+
+        let pendiente = client.proponer(&c, &AccionConsejo::ResetContador, &100);
+
+        // Reemplazar a C por D
+        let nuevo = Consejo { firmantes: vec![&env, a.clone(), b.clone(), d.clone()], umbral: 2 };
+        let id = client.proponer(&a, &AccionConsejo::CambiarConsejo(nuevo.clone()), &100);
+        client.aprobar(&b, &id);
+        assert_eq!(client.get_consejo(), Some(nuevo));
+
+        // Lo que estaba pendiente se cancela y C ya no es firmante
+        assert_eq!(client.try_aprobar(&a, &pendiente), Err(Ok(Error::PropuestaCerrada)));
+        assert_eq!(
+            client.try_proponer(&c, &AccionConsejo::ResetContador, &100),
+            Err(Ok(Error::NoAutorizado))
+        );
+        client.proponer(&d, &AccionConsejo::ResetContador, &100);
+    }
+
+    #[test]
+    fn test_consejo_invalido() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let a = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // Umbral mayor que la cantidad de firmantes
+        let consejo = Consejo { firmantes: vec![&env, a.clone()], umbral: 2 };
+        assert_eq!(client.try_configurar_consejo(&admin, &consejo), Err(Ok(Error::ConsejoInvalido)));
+
+        // Firmante repetido
+        let consejo = Consejo { firmantes: vec![&env, a.clone(), a.clone()], umbral: 2 };
+        assert_eq!(client.try_configurar_consejo(&admin, &consejo), Err(Ok(Error::ConsejoInvalido)));
+    }
+
+    #[test]
+    fn test_propuestas_inexistentes_y_tope() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let (a, b, _) = consejo_2_de_3(&env, &client);
+
+        assert_eq!(client.try_aprobar(&b, &99), Err(Ok(Error::PropuestaNoExiste)));
+
+        for _ in 0..MAX_PROPUESTAS {
+            client.proponer(&a, &AccionConsejo::ResetContador, &10);
+        }
+        assert_eq!(
+            client.try_proponer(&a, &AccionConsejo::ResetContador, &10),
+            Err(Ok(Error::DemasiadasPropuestas))
+        );
+
+        // Las vencidas liberan lugar
+        env.ledger().set_sequence_number(11);
+        client.proponer(&a, &AccionConsejo::ResetContador, &10);
+        assert_eq!(client.get_propuestas_pendientes().len(), 1);
+    }
+
+    // ========================================================================
+    // TEST: Cambios con timelock
+    // ========================================================================
+    #[test]
+    fn test_timelock_limite() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        // Retraso 0: el primer cambio de retraso se puede ejecutar ya
+        let id = client.programar_retraso(&admin, &100);
+        client.ejecutar_cambio(&id);
+        assert_eq!(client.get_retraso_cambios(), 100);
+
+        // Con retraso activo el cambio directo ya no está permitido
+        assert_eq!(
+            client.try_set_limite_nombre(&admin, &10),
+            Err(Ok(Error::RequiereProgramacion))
+        );
+
+        // Programar: queda en cola y se anuncia con un evento
+        let id = client.programar_limite(&admin, &10);
+        let evento = CambioEnCola { id, cambio: CambioConfig::LimiteNombre(10), ejecutable_desde: 100 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), evento.topics(&env), evento.data(&env))]
+        );
+        assert_eq!(client.get_cambios_programados().len(), 1);
+
+        // Antes del retraso no se puede ejecutar
+        env.ledger().set_sequence_number(99);
+        assert_eq!(client.try_ejecutar_cambio(&id), Err(Ok(Error::CambioNoListo)));
+        assert_eq!(client.get_limite_nombre(), 32);
+
+        // Después sí, y lo puede ejecutar cualquiera
+        env.ledger().set_sequence_number(100);
+        client.ejecutar_cambio(&id);
+        let evento = CambioEjecutado { id, cambio: CambioConfig::LimiteNombre(10) };
+        let auditado = Auditado {
+            accion: AccionAuditoria::LimitePorTimelock,
+            id: 2,
+            actor: contract_id.clone(),
+            usuario: None,
+            sala: None,
+            anterior: ValorAuditoria::Numero(32),
+            nuevo: ValorAuditoria::Numero(10),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (contract_id.clone(), evento.topics(&env), evento.data(&env)),
+                (contract_id, auditado.topics(&env), auditado.data(&env)),
+            ]
+        );
+        assert_eq!(client.get_limite_nombre(), 10);
+        assert_eq!(client.get_cambios_programados().len(), 0);
+    }
+
+    #[test]
+    fn test_timelock_con_consejo() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let firmante = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Timelock activo antes de pasar al consejo
+        let id = client.programar_retraso(&admin, &100);
+        client.ejecutar_cambio(&id);
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, firmante.clone()], umbral: 1 },
+        );
+        assert_eq!(client.try_programar_limite(&admin, &10), Err(Ok(Error::RequiereConsejo)));
+
+        // La propuesta aprobada pone el cambio en cola, no lo aplica
+        client.proponer(&firmante, &AccionConsejo::SetLimiteNombre(10), &10);
+        assert_eq!(client.get_limite_nombre(), 32);
+        let cambios = client.get_cambios_programados();
+        assert_eq!(cambios.len(), 1);
+        let cambio = cambios.get(0).unwrap();
+        assert_eq!(cambio.cambio, CambioConfig::LimiteNombre(10));
+        assert_eq!(cambio.ejecutable_desde, 100);
+
+        assert_eq!(cambio.origen, OrigenCambio::Consejo);
+
+        env.ledger().set_sequence_number(99);
+        assert_eq!(client.try_ejecutar_cambio(&cambio.id), Err(Ok(Error::CambioNoListo)));
+
+        env.ledger().set_sequence_number(100);
+        client.ejecutar_cambio(&cambio.id);
+        assert_eq!(client.get_limite_nombre(), 10);
+    }
+
+    #[test]
+    fn test_timelock_cancelar_con_consejo() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let firmante = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let id = client.programar_retraso(&admin, &100);
+        client.ejecutar_cambio(&id);
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, firmante.clone()], umbral: 1 },
+        );
+
+        client.proponer(&firmante, &AccionConsejo::SetLimiteNombre(10), &10);
+        let cambio = client.get_cambios_programados().get(0).unwrap().id;
+
+        // El admin solo no deshace lo que decidió el consejo
+        assert_eq!(client.try_cancelar_cambio(&admin, &cambio), Err(Ok(Error::RequiereConsejo)));
+        assert_eq!(client.get_cambios_programados().len(), 1);
+
+        // El consejo sí, con su propia propuesta
+        client.proponer(&firmante, &AccionConsejo::CancelarCambio(cambio), &10);
+        assert_eq!(client.get_cambios_programados().len(), 0);
+        assert_eq!(
+            client.try_proponer(&firmante, &AccionConsejo::CancelarCambio(cambio), &10),
+            Err(Ok(Error::CambioNoExiste))
+        );
+    }
+
+    #[test]
+    fn test_timelock_cancelar() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let otro = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        let id = client.programar_limite(&admin, &10);
+        assert_eq!(client.try_cancelar_cambio(&otro, &id), Err(Ok(Error::NoAutorizado)));
+
+        client.cancelar_cambio(&admin, &id);
+        assert_eq!(client.try_ejecutar_cambio(&id), Err(Ok(Error::CambioNoExiste)));
+        assert_eq!(client.get_limite_nombre(), 32);
+    }
+
+    #[test]
+    fn test_timelock_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        assert_eq!(
+            client.try_programar_retraso(&admin, &(MAX_RETRASO + 1)),
+            Err(Ok(Error::RetrasoInvalido))
+        );
+        client.programar_retraso(&admin, &MAX_RETRASO);
+
+        for _ in 1..MAX_CAMBIOS {
+            client.programar_limite(&admin, &10);
+        }
+        assert_eq!(client.try_programar_limite(&admin, &10), Err(Ok(Error::DemasiadosCambios)));
+
+        // Cancelar uno libera lugar en la cola
+        client.cancelar_cambio(&admin, &1);
+        client.programar_limite(&admin, &10);
+    }
+
+    // ========================================================================
+    // TEST: Gobernanza comunitaria
+    // ========================================================================
+    fn saludar_n(env: &Env, client: &HelloContractClient, usuario: &Address, n: u32) {
+        for _ in 0..n {
+            client.hello(usuario, &String::from_str(env, "Ana"));
+        }
+    }
+
+    #[test]
+    fn test_votacion_aprobada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 5, periodo: 10 });
+
+        env.ledger().set_sequence_number(1);
+        saludar_n(&env, &client, &ana, 4);
+        saludar_n(&env, &client, &eva, 2);
+
+        env.ledger().set_sequence_number(2);
+        let id = client.proponer_limite(&ana, &64);
+
+        // Saludar después de la instantánea no suma peso
+        saludar_n(&env, &client, &eva, 5);
+        env.ledger().set_sequence_number(3);
+        saludar_n(&env, &client, &eva, 5);
+
+        assert_eq!(client.votar(&ana, &id, &true), 4);
+        assert_eq!(client.votar(&eva, &id, &false), 2);
+        assert_eq!(client.try_votar(&ana, &id, &true), Err(Ok(Error::YaVoto)));
+
+        // No se puede cerrar antes de tiempo
+        assert_eq!(client.try_finalizar_votacion(&id), Err(Ok(Error::VotacionAbierta)));
+
+        env.ledger().set_sequence_number(13);
+        assert_eq!(client.try_votar(&eva, &id, &false), Err(Ok(Error::VotacionCerrada)));
+        assert_eq!(client.finalizar_votacion(&id), EstadoVotacion::Aprobada);
+        assert_eq!(client.get_limite_nombre(), 64);
+    }
+
+    #[test]
+    fn test_votacion_respeta_timelock_y_consejo() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let firmante = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 1, periodo: 10 });
+        let cambio = client.programar_retraso(&admin, &100);
+        client.ejecutar_cambio(&cambio);
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, firmante.clone(), admin.clone()], umbral: 2 },
+        );
+
+        env.ledger().set_sequence_number(1);
+        saludar_n(&env, &client, &ana, 1);
+        env.ledger().set_sequence_number(2);
+        let id = client.proponer_limite(&ana, &64);
+        client.votar(&ana, &id, &true);
+
+        // Aprobada sin pasar por el consejo, pero el límite espera el retraso
+        env.ledger().set_sequence_number(13);
+        assert_eq!(client.finalizar_votacion(&id), EstadoVotacion::Aprobada);
+        assert_eq!(client.get_limite_nombre(), 32);
+        let cambios = client.get_cambios_programados();
+        assert_eq!(cambios.len(), 1);
+        let programado = cambios.get(0).unwrap();
+        assert_eq!(programado.cambio, CambioConfig::LimiteNombre(64));
+        assert_eq!(programado.ejecutable_desde, 113);
+        assert_eq!(programado.origen, OrigenCambio::Votacion);
+
+        // Lo que ganó la votación no lo cancela ni el consejo
+        let propuesta = client.proponer(&firmante, &AccionConsejo::CancelarCambio(programado.id), &10);
+        assert_eq!(client.try_aprobar(&admin, &propuesta), Err(Ok(Error::NoAutorizado)));
+
+        env.ledger().set_sequence_number(113);
+        client.ejecutar_cambio(&programado.id);
+        assert_eq!(client.get_limite_nombre(), 64);
+    }
+
+    #[test]
+    fn test_votacion_sin_quorum() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let nueva = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        env.ledger().set_sequence_number(1);
+        saludar_n(&env, &client, &ana, 3);
+
+        env.ledger().set_sequence_number(2);
+        let id = client.proponer_limite(&ana, &64);
+
+        // Quien no había saludado antes de la votación no tiene peso
+        saludar_n(&env, &client, &nueva, 1);
+        assert_eq!(client.try_votar(&nueva, &id, &true), Err(Ok(Error::SinPesoDeVoto)));
+        assert_eq!(
+            client.try_proponer_limite(&Address::generate(&env), &64),
+            Err(Ok(Error::SinPesoDeVoto))
+        );
+
+        // 3 votos a favor no llegan al quórum por defecto (10)
+        client.votar(&ana, &id, &true);
+        env.ledger().set_sequence_number(2 + PERIODO_POR_DEFECTO + 1);
+        assert_eq!(client.finalizar_votacion(&id), EstadoVotacion::Rechazada);
+        assert_eq!(client.get_limite_nombre(), 32);
+    }
+
+    #[test]
+    fn test_quorum_fijo_al_proponer() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 5, periodo: 10 });
+
+        env.ledger().set_sequence_number(1);
+        saludar_n(&env, &client, &ana, 6);
+
+        env.ledger().set_sequence_number(2);
+        let alcanza = client.proponer_limite(&ana, &64);
+        client.votar(&ana, &alcanza, &true);
+
+        // Con los votos ya emitidos, el admin sube el quórum por encima de 6
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 100, periodo: 10 });
+        let no_alcanza = client.proponer_limite(&ana, &100);
+        client.votar(&ana, &no_alcanza, &true);
+
+        // ...y lo vuelve a bajar: la segunda votación tampoco cambia
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 1, periodo: 10 });
+
+        env.ledger().set_sequence_number(13);
+        assert_eq!(client.get_votacion(&alcanza).unwrap().quorum, 5);
+        assert_eq!(client.finalizar_votacion(&alcanza), EstadoVotacion::Aprobada);
+        assert_eq!(client.finalizar_votacion(&no_alcanza), EstadoVotacion::Rechazada);
+        assert_eq!(client.get_limite_nombre(), 64);
+    }
+
+    #[test]
+    fn test_checkpoints_entre_votaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+
+        env.ledger().set_sequence_number(1);
+        saludar_n(&env, &client, &ana, 2);
+
+        env.ledger().set_sequence_number(2);
+        let primera = client.proponer_limite(&ana, &64);
+
+        env.ledger().set_sequence_number(3);
+        saludar_n(&env, &client, &ana, 3);
+
+        env.ledger().set_sequence_number(4);
+        let segunda = client.proponer_limite(&ana, &100);
+
+        env.ledger().set_sequence_number(5);
+        saludar_n(&env, &client, &ana, 1);
+
+        // Cada votación ve el contador de su propia instantánea
+        assert_eq!(client.votar(&ana, &primera, &true), 2);
+        assert_eq!(client.votar(&ana, &segunda, &true), 5);
+    }
+
+    #[test]
+    fn test_gobernanza_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        client.initialize(&admin);
+        saludar_n(&env, &client, &ana, 1);
+
+        assert_eq!(client.try_votar(&ana, &99, &true), Err(Ok(Error::VotacionNoExiste)));
+        assert_eq!(client.try_finalizar_votacion(&99), Err(Ok(Error::VotacionNoExiste)));
+
+        for config in [
+            ConfigGobernanza { quorum: 0, periodo: 10 },
+            ConfigGobernanza { quorum: 5, periodo: 0 },
+            ConfigGobernanza { quorum: 5, periodo: MAX_RETRASO + 1 },
+        ] {
+            assert_eq!(
+                client.try_configurar_gobernanza(&admin, &config),
+                Err(Ok(Error::GobernanzaInvalida))
+            );
+        }
+        assert_eq!(client.get_gobernanza().quorum, QUORUM_POR_DEFECTO);
+    }
+
+    // ========================================================================
+    // TEST: Moderación y apelaciones
+    // ========================================================================
+    #[test]
+    fn test_ocultar_y_apelar() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let moderador = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+
+        client.initialize(&admin);
+        client.agregar_moderador(&admin, &moderador);
+        client.hello(&ana, &nombre);
+
+        let id = client.ocultar_saludo(&moderador, &ana, &MotivoModeracion::Ofensivo);
+        assert!(client.saludo_oculto(&ana));
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(String::from_str(&env, "[oculto]")));
+
+        // El usuario apela una sola vez
+        client.apelar(&ana, &id, &String::from_str(&env, "Es mi nombre real"));
+        assert_eq!(
+            client.try_apelar(&ana, &id, &String::from_str(&env, "Otra vez")),
+            Err(Ok(Error::YaApelada))
+        );
+
+        // Aceptar la apelación vuelve a mostrar el saludo
+        client.resolver_apelacion(&moderador, &id, &true);
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(nombre));
+
+        let log = client.get_moderaciones(&ana);
+        assert_eq!(log.len(), 1);
+        let accion = log.get_unchecked(0);
+        assert_eq!(accion.tipo, TipoModeracion::Ocultar);
+        assert_eq!(accion.motivo, MotivoModeracion::Ofensivo);
+        assert_eq!(accion.apelacion, EstadoApelacion::Aceptada);
+    }
+
+    #[test]
+    fn test_borrar_y_suspender() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let spammer = Address::generate(&env);
+        let nombre = String::from_str(&env, "Spam");
+
+        client.initialize(&admin);
+        client.hello(&spammer, &nombre);
+
+        client.borrar_saludo(&admin, &spammer, &MotivoModeracion::Spam);
+        assert_eq!(client.get_ultimo_saludo(&spammer), None);
+        assert_eq!(
+            client.try_borrar_saludo(&admin, &spammer, &MotivoModeracion::Spam),
+            Err(Ok(Error::SinSaludo))
+        );
+
+        // Suspendido por 10 ledgers: hasta el ledger 10 inclusive
+        let id = client.suspender_usuario(&admin, &spammer, &10, &MotivoModeracion::Spam);
+        assert_eq!(client.try_hello(&spammer, &nombre), Err(Ok(Error::UsuarioSuspendido)));
+
+        // Rechazar la apelación mantiene la suspensión
+        client.apelar(&spammer, &id, &String::from_str(&env, "Perdon"));
+        client.resolver_apelacion(&admin, &id, &false);
+        assert_eq!(client.try_hello(&spammer, &nombre), Err(Ok(Error::UsuarioSuspendido)));
+
+        env.ledger().set_sequence_number(11);
+        client.hello(&spammer, &nombre);
+        assert_eq!(client.get_moderaciones(&spammer).len(), 2);
+    }
+
+    #[test]
+    fn test_moderacion_no_autorizada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+
+        assert_eq!(
+            client.try_ocultar_saludo(&eva, &ana, &MotivoModeracion::Otro),
+            Err(Ok(Error::NoAutorizado))
+        );
+
+        // Solo el usuario afectado puede apelar
+        let id = client.ocultar_saludo(&admin, &ana, &MotivoModeracion::Otro);
+        assert_eq!(
+            client.try_apelar(&eva, &id, &String::from_str(&env, "No")),
+            Err(Ok(Error::NoAutorizado))
+        );
+    }
+
+    #[test]
+    fn test_apelaciones_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let mensaje = String::from_str(&env, "Es mi nombre real");
+
+        client.initialize(&admin);
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+
+        assert_eq!(client.try_apelar(&ana, &99, &mensaje), Err(Ok(Error::AccionNoExiste)));
+        assert_eq!(
+            client.try_resolver_apelacion(&admin, &99, &true),
+            Err(Ok(Error::AccionNoExiste))
+        );
+
+        let id = client.ocultar_saludo(&admin, &ana, &MotivoModeracion::Otro);
+
+        // Mensaje vacío o más largo que MAX_APELACION
+        let largo = String::from_bytes(&env, &[b'a'; MAX_APELACION as usize + 1]);
+        for invalido in [String::from_str(&env, ""), largo] {
+            assert_eq!(
+                client.try_apelar(&ana, &id, &invalido),
+                Err(Ok(Error::ApelacionInvalida))
+            );
+        }
+
+        // Sin apelación no hay nada que resolver, y se resuelve una sola vez
+        assert_eq!(
+            client.try_resolver_apelacion(&admin, &id, &true),
+            Err(Ok(Error::ApelacionNoPendiente))
+        );
+        client.apelar(&ana, &id, &mensaje);
+        client.resolver_apelacion(&admin, &id, &false);
+        assert_eq!(
+            client.try_resolver_apelacion(&admin, &id, &true),
+            Err(Ok(Error::ApelacionNoPendiente))
+        );
+        assert!(client.saludo_oculto(&ana));
+    }
+
+    // ========================================================================
+    // TEST: Filtro de palabras prohibidas
+    // ========================================================================
+    #[test]
+    fn test_palabras_prohibidas() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, b"SpAm"));
+        assert_eq!(
+            client.get_palabras_prohibidas(),
+            vec![&env, Bytes::from_slice(&env, b"spam")]
+        );
+
+        // Sin importar mayúsculas ni la posición dentro del nombre
+        for nombre in ["spam", "MiSPAMbot", "xxSpam"] {
+            assert_eq!(
+                client.try_hello(&ana, &String::from_str(&env, nombre)),
+                Err(Ok(Error::NombreProhibido))
+            );
+        }
+        client.hello(&ana, &String::from_str(&env, "Spa m"));
+
+        // El largo se valida antes que las palabras
+        client.set_limite_nombre(&admin, &4);
+        assert_eq!(
+            client.try_hello(&ana, &String::from_str(&env, "spam!")),
+            Err(Ok(Error::NombreMuyLargo))
+        );
+
+        client.quitar_palabra_prohibida(&admin, &Bytes::from_slice(&env, b"SPAM"));
+        client.hello(&ana, &String::from_str(&env, "spam"));
+        assert_eq!(client.get_contador(), 2);
+    }
+
+    #[test]
+    fn test_palabras_prohibidas_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let eva = Address::generate(&env);
+        client.initialize(&admin);
+
+        assert_eq!(
+            client.try_agregar_palabra_prohibida(&eva, &Bytes::from_slice(&env, b"spam")),
+            Err(Ok(Error::NoAutorizado))
+        );
+        assert_eq!(
+            client.try_agregar_palabra_prohibida(&admin, &Bytes::new(&env)),
+            Err(Ok(Error::PalabraInvalida))
+        );
+        assert_eq!(
+            client.try_agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, &[b'a'; 33])),
+            Err(Ok(Error::PalabraInvalida))
+        );
+
+        client.agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, b"spam"));
+        assert_eq!(
+            client.try_agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, b"SPAM")),
+            Err(Ok(Error::PalabraInvalida))
+        );
+
+        for i in 1..MAX_PALABRAS_PROHIBIDAS {
+            client.agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, &[b'a' + i as u8; 3]));
+        }
+        assert_eq!(
+            client.try_agregar_palabra_prohibida(&admin, &Bytes::from_slice(&env, b"otra")),
+            Err(Ok(Error::DemasiadasPalabras))
+        );
+    }
+
+    // WASM que deja `make build` (stellar contract build). En modo nativo el
+    // Rust del contrato no se mide, solo sus llamadas al host: lo que tenga
+    // que medir su propio código corre contra este binario.
+    // Esos tests llevan #[ignore] para que `cargo test` no dependa de un
+    // binario que puede faltar o estar viejo: `make test` compila y los corre
+    const WASM: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../target/wasm32v1-none/release/hello_world.wasm"
+    );
+
+    fn registrar_wasm(env: &Env, admin: &Address) -> Address {
+        let wasm = std::fs::read(WASM)
+            .expect("falta el WASM compilado: correr `make test`, que compila antes");
+        // Subir y validar el binario no es lo que se mide
+        env.cost_estimate().budget().reset_unlimited();
+        env.register(wasm.as_slice(), (Some(admin.clone()),))
+    }
+
+    // Costo de CPU de un hello() con un nombre de largo máximo
+    fn costo_hello(env: &Env, client: &HelloContractClient, usuario: &Address) -> u64 {
+        let nombre = String::from_bytes(env, &[b'z'; LIMITE_MAXIMO as usize]);
+        env.cost_estimate().budget().reset_default();
+        client.hello(usuario, &nombre);
+        env.cost_estimate().budget().cpu_instruction_cost()
+    }
+
+    // Costo de hello() sin lista y con la lista de palabras en su peor caso:
+    // llena con palabras del largo máximo que casi coinciden (obliga a
+    // comparar casi todos los bytes de cada ventana)
+    fn costo_filtro(env: &Env, contract_id: &Address, admin: &Address) -> (u64, u64) {
+        let client = HelloContractClient::new(env, contract_id);
+        let ana = Address::generate(env);
+
+        client.set_limite_nombre(admin, &LIMITE_MAXIMO);
+        client.hello(&ana, &String::from_str(env, "Ana"));
+
+        let sin_lista = costo_hello(env, &client, &ana);
+        for i in 0..MAX_PALABRAS_PROHIBIDAS {
+            let mut palabra = [b'z'; MAX_LARGO_PALABRA as usize];
+            palabra[MAX_LARGO_PALABRA as usize - 1] = b'a' + i as u8;
+            client.agregar_palabra_prohibida(admin, &Bytes::from_slice(env, &palabra));
+        }
+        (sin_lista, costo_hello(env, &client, &ana))
+    }
+
+    #[test]
+    fn test_palabras_prohibidas_costo_acotado() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+
+        let (sin_lista, lista_llena) = costo_filtro(&env, &contract_id, &admin);
+
+        // En nativo solo cuentan las llamadas al host del filtro: leer la
+        // lista y copiar cada palabra, no la comparación de bytes
+        assert!(lista_llena > sin_lista);
+        assert!(lista_llena < sin_lista * 2);
+        assert!(lista_llena < 10_000_000);
+    }
+
+    #[test]
+    #[ignore = "necesita el WASM de `make build`: correr con `make test`"]
+    fn test_palabras_prohibidas_costo_acotado_wasm() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = registrar_wasm(&env, &admin);
+
+        let (sin_lista, lista_llena) = costo_filtro(&env, &contract_id, &admin);
+
+        // Medido en WASM la comparación de bytes sí cuenta: el peor caso
+        // del filtro anda por los 18M de instrucciones
+        assert!(lista_llena > sin_lista);
+        assert!(lista_llena - sin_lista < 25_000_000);
+        // Y el hello() completo queda lejos del límite de 100M por transacción
+        assert!(lista_llena < 30_000_000);
+    }
+
+    // ========================================================================
+    // TEST: Saludos localizados
+    // ========================================================================
+    #[test]
+    fn test_saludo_localizado() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let joao = Address::generate(&env);
+        let nombre = String::from_str(&env, "Tiburona");
+        let pt = symbol_short!("pt");
+
+        client.initialize(&admin);
+        client.set_saludo_idioma(&admin, &pt, &String::from_str(&env, "Olá"));
+        client.set_saludo_idioma(&admin, &symbol_short!("en"), &String::from_str(&env, "Hello"));
+        assert_eq!(client.get_idiomas().len(), 3);
+
+        // Sin preferencia: idioma por defecto ("es")
+        assert_eq!(client.hello(&ana, &nombre), String::from_str(&env, "Hola"));
+
+        client.set_idioma(&joao, &pt);
+        assert_eq!(client.get_idioma(&joao), Some(pt.clone()));
+        assert_eq!(client.hello(&joao, &nombre), String::from_str(&env, "Olá"));
+
+        // Cambiar el idioma por defecto afecta a quien no eligió
+        client.set_idioma_por_defecto(&admin, &symbol_short!("en"));
+        assert_eq!(client.hello(&ana, &nombre), String::from_str(&env, "Hello"));
+
+        // Si quitan el idioma elegido, se usa el por defecto
+        client.quitar_idioma(&admin, &pt);
+        assert_eq!(client.hello(&joao, &nombre), String::from_str(&env, "Hello"));
+    }
+
+    #[test]
+    fn test_saludo_localizado_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        client.initialize(&admin);
+
+        assert_eq!(
+            client.try_set_idioma(&ana, &symbol_short!("fr")),
+            Err(Ok(ErrorExtra::IdiomaNoSoportado.into()))
+        );
+        assert_eq!(
+            client.try_set_saludo_idioma(&ana, &symbol_short!("fr"), &String::from_str(&env, "Salut")),
+            Err(Ok(Error::NoAutorizado.into()))
+        );
+        assert_eq!(
+            client.try_set_saludo_idioma(&admin, &symbol_short!("fr"), &String::from_str(&env, "")),
+            Err(Ok(ErrorExtra::SaludoInvalido.into()))
+        );
+        assert_eq!(
+            client.try_set_idioma_por_defecto(&admin, &symbol_short!("fr")),
+            Err(Ok(ErrorExtra::IdiomaNoSoportado.into()))
+        );
+        assert_eq!(
+            client.try_quitar_idioma(&admin, &symbol_short!("es")),
+            Err(Ok(ErrorExtra::IdiomaPorDefecto.into()))
+        );
+
+        // "es" ya ocupa un lugar
+        let hola = String::from_str(&env, "Hola");
+        for i in 1..MAX_IDIOMAS {
+            client.set_saludo_idioma(&admin, &Symbol::new(&env, &std::format!("i{}", i)), &hola);
+        }
+        assert_eq!(
+            client.try_set_saludo_idioma(&admin, &symbol_short!("fr"), &String::from_str(&env, "Salut")),
+            Err(Ok(ErrorExtra::DemasiadosIdiomas.into()))
+        );
+
+        // Cambiar el saludo de un idioma existente no suma lugares
+        client.set_saludo_idioma(&admin, &symbol_short!("es"), &String::from_str(&env, "Buenas"));
+        assert_eq!(client.get_idiomas().len(), MAX_IDIOMAS);
+    }
 
-#[contractimpl]
-impl HelloContract {
-    
-    // ========================================================================
-    // FUNCIÓN: initialize
     // ========================================================================
-    // Propósito: Configurar el contrato por primera vez
-    // Parámetros:
-    //   - env: Ambiente de Soroban (acceso a storage, eventos, etc.)
-    //   - admin: Dirección que será el administrador del contrato
-    // Retorna: Result<(), Error> - Ok si exitoso, Err si falla
+    // TEST: Plantillas de respuesta
     // ========================================================================
-    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
-        
-        // PASO 1: Verificar que el contrato NO esté ya inicializado
-        // --------------------------------------------------------
-        // .has() verifica si existe una key en storage sin deserializar el valor
-        // Es más eficiente que .get() porque solo chequea existencia
-        if env.storage().instance().has(&DataKey::Admin) {
-            // Si ya hay un admin guardado, el contrato ya fue inicializado
-            // Retornamos error #4 (NoInicializado) para evitar re-inicialización
-            return Err(Error::NoInicializado);
+    #[test]
+    fn test_hello_plantilla() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        let id = symbol_short!("ranking");
+
+        client.initialize(&admin);
+        client.set_plantilla(
+            &admin,
+            &id,
+            &String::from_str(&env, "Hola {nombre}, eres la saludadora #{contador} ({usuario_contador} tuyos)")
+        );
+
+        for _ in 0..41 {
+            client.hello(&eva, &String::from_str(&env, "Eva"));
         }
-        
-        // PASO 2: Guardar el administrador en Instance Storage
-        // -----------------------------------------------------
-        // Instance storage = datos globales del contrato
-        // .set() toma dos parámetros: key y valor (ambos por referencia &)
-        env.storage()
-            .instance()                          // Acceder a instance storage
-            .set(&DataKey::Admin, &admin);       // Guardar admin con key "Admin"
-        
-        // PASO 3: Inicializar contador de saludos en 0
-        // ---------------------------------------------
-        // &0u32 = referencia a un unsigned 32-bit integer con valor 0
-        // Inicializamos explícitamente en 0 para documentar el estado inicial
-        env.storage()
-            .instance()
-            .set(&DataKey::ContadorSaludos, &0u32);
-        
-        // PASO 4: Extender el TTL (Time To Live) del instance storage
-        // ------------------------------------------------------------
-        // TTL = cuánto tiempo persisten los datos en blockchain
-        // extend_ttl(100, 100) significa:
-        //   - Si quedan menos de 100 ledgers de vida (threshold)
-        //   - Extiende por 100 ledgers más (extend_to)
-        // Esto asegura que la configuración no expire accidentalmente
-        env.storage()
-            .instance()
-            .extend_ttl(100, 100);
-        
-        // PASO 5: Retornar éxito
-        // -----------------------
-        // Ok(()) = operación exitosa sin valor de retorno
-        Ok(())
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+
+        assert_eq!(
+            client.hello_plantilla(&ana, &String::from_str(&env, "Ana"), &id),
+            String::from_str(&env, "Hola Ana, eres la saludadora #43 (2 tuyos)")
+        );
+        assert_eq!(client.get_contador(), 43);
+
+        // Sin marcadores la plantilla se copia tal cual (acentos incluidos)
+        client.set_plantilla(&admin, &id, &String::from_str(&env, "¡Olá!"));
+        assert_eq!(
+            client.hello_plantilla(&eva, &String::from_str(&env, "Eva"), &id),
+            String::from_str(&env, "¡Olá!")
+        );
+    }
+
+    #[test]
+    fn test_plantilla_invalida() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let id = symbol_short!("mala");
+        client.initialize(&admin);
+
+        let largo = [b'a'; MAX_LARGO_PLANTILLA as usize + 1];
+        for mala in ["", "Hola {nombre", "Hola {apellido}", "Hola }", "{{nombre}}"] {
+            assert_eq!(
+                client.try_set_plantilla(&admin, &id, &String::from_str(&env, mala)),
+                Err(Ok(ErrorExtra::PlantillaInvalida.into()))
+            );
+        }
+        assert_eq!(
+            client.try_set_plantilla(&admin, &id, &String::from_bytes(&env, &largo)),
+            Err(Ok(ErrorExtra::PlantillaInvalida.into()))
+        );
+
+        // Plantilla inexistente: no se registra el saludo
+        assert_eq!(
+            client.try_hello_plantilla(&ana, &String::from_str(&env, "Ana"), &id),
+            Err(Ok(ErrorExtra::PlantillaNoExiste.into()))
+        );
+        assert_eq!(client.get_contador(), 0);
     }
-    
+
     // ========================================================================
-    // FUNCIÓN: hello
+    // TEST: Perfiles
     // ========================================================================
-    // Propósito: Saludar a una Tiburona y registrar el evento
-    // Parámetros:
-    //   - env: Ambiente de Soroban
-    //   - usuario: Dirección de quien saluda
-    //   - nombre: Nombre de la Tiburona (String para validar longitud)
-    // Retorna: Result<Symbol, Error> - "Hola" si exitoso, Error si falla
+    #[test]
+    fn test_perfil_y_hello_perfil() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let avatar = BytesN::from_array(&env, &[7; 32]);
+        let en = symbol_short!("en");
+
+        client.initialize(&admin);
+        client.set_saludo_idioma(&admin, &en, &String::from_str(&env, "Hello"));
+
+        env.ledger().set_sequence_number(5);
+        client.set_perfil(
+            &ana,
+            &String::from_str(&env, "Ana la Tiburona"),
+            &String::from_str(&env, "Dev en Stellar"),
+            &Some(avatar.clone()),
+            &Some(en.clone())
+        );
+
+        // El saludo usa el nombre y el idioma del perfil
+        assert_eq!(client.hello_perfil(&ana), String::from_str(&env, "Hello"));
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(String::from_str(&env, "Ana la Tiburona")));
+        assert_eq!(client.get_contador_usuario(&ana), 1);
+
+        // Actualizar conserva `creado`; set_idioma se refleja en el perfil
+        env.ledger().set_sequence_number(9);
+        client.set_perfil(&ana, &String::from_str(&env, "Ana"), &String::from_str(&env, ""), &None, &Some(en));
+        client.set_idioma(&ana, &symbol_short!("es"));
+
+        let perfil = client.get_perfil(&ana).unwrap();
+        assert_eq!(perfil.creado, 5);
+        assert_eq!(perfil.avatar, None);
+        assert_eq!(perfil.idioma, Some(symbol_short!("es")));
+        assert_eq!(client.hello_perfil(&ana), String::from_str(&env, "Hola"));
+    }
+
+    #[test]
+    fn test_perfil_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let vacio = String::from_str(&env, "");
+        client.initialize(&admin);
+
+        assert_eq!(client.try_hello_perfil(&ana), Err(Ok(ErrorExtra::SinPerfil.into())));
+        assert_eq!(
+            client.try_set_perfil(&ana, &vacio, &vacio, &None, &None),
+            Err(Ok(Error::NombreVacio.into()))
+        );
+        assert_eq!(
+            client.try_set_perfil(
+                &ana,
+                &String::from_str(&env, "Ana"),
+                &String::from_bytes(&env, &[b'x'; MAX_LARGO_BIO as usize + 1]),
+                &None,
+                &None
+            ),
+            Err(Ok(ErrorExtra::BioMuyLarga.into()))
+        );
+        assert_eq!(
+            client.try_set_perfil(&ana, &String::from_str(&env, "Ana"), &vacio, &None, &Some(symbol_short!("fr"))),
+            Err(Ok(ErrorExtra::IdiomaNoSoportado.into()))
+        );
+        assert_eq!(client.get_perfil(&ana), None);
+    }
+
     // ========================================================================
-    pub fn hello(
-        env: Env,
-        usuario: Address,
-        nombre: String
-    ) -> Result<Symbol, Error> {
-        
-        // VALIDACIÓN 1: Nombre no puede estar vacío
-        // ------------------------------------------
-        // .len() retorna la longitud del String
-        // Validamos PRIMERO porque es lo más barato (no requiere storage)
-        if nombre.len() == 0 {
-            // Early return: salir inmediatamente si hay error
-            // Error #1 = NombreVacio
-            return Err(Error::NombreVacio);
-        }
-        
-        // VALIDACIÓN 2: Nombre no puede ser muy largo
-        // --------------------------------------------
-        // Límite de 32 caracteres para:
-        //   - Prevenir consumo excesivo de gas
-        //   - Evitar ataques de DoS con strings gigantes
-        if nombre.len() > 32 {
-            // Error #2 = NombreMuyLargo
-            return Err(Error::NombreMuyLargo);
-        }
-        
-        // PASO 1: Incrementar contador global de saludos
-        // -----------------------------------------------
-        // Patrón: Leer → Modificar → Guardar
-        
-        // 1a. Definir la key para el contador
-        let key_contador = DataKey::ContadorSaludos;
-        
-        // 1b. Leer el contador actual del storage
-        // .get() retorna Option<u32>
-        // .unwrap_or(0) convierte None en 0 (lazy initialization)
-        let contador: u32 = env.storage()
-            .instance()
-            .get(&key_contador)
-            .unwrap_or(0);               // Primera vez = 0
-        
-        // 1c. Guardar el nuevo valor (contador + 1)
-        // &(contador + 1) = referencia a la expresión calculada
-        env.storage()
-            .instance()
-            .set(&key_contador, &(contador + 1));
-        
-        // PASO 2: Guardar el saludo específico del usuario
-        // -------------------------------------------------
-        // Persistent storage = datos críticos por usuario
-        // .clone() crea una copia de usuario porque lo usamos dos veces
-        env.storage()
-            .persistent()                                      // Storage persistente
-            .set(&DataKey::UltimoSaludo(usuario.clone()), &nombre);  // Key compuesta
-        
-        // PASO 3: Extender TTL del saludo del usuario
-        // --------------------------------------------
-        // Cada usuario tiene su propio TTL
-        // Esto asegura que su saludo no expire mientras esté activo
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKey::UltimoSaludo(usuario), 100, 100);
-        
-        // PASO 4: Extender TTL del instance storage
-        // ------------------------------------------
-        // Mantener vivo el contador global también
-        env.storage()
-            .instance()
-            .extend_ttl(100, 100);
-        
-        // PASO 5: Retornar saludo exitoso
-        // --------------------------------
-        // Symbol::new() crea un Symbol en el ambiente
-        // Ok() envuelve el Symbol en Result para indicar éxito
-        Ok(Symbol::new(&env, "Hola"))
+    // TEST: Registro de nombres
+    // ========================================================================
+    #[test]
+    fn test_registro_de_nombres() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        client.initialize(&admin);
+
+        client.reclamar_nombre(&ana, &String::from_str(&env, "Ana"));
+        assert_eq!(client.quien_es(&String::from_str(&env, "ANA")), Some(ana.clone()));
+        assert_eq!(client.get_nombre_registrado(&ana), Some(String::from_str(&env, "ana")));
+
+        // Mayúsculas distintas siguen siendo el mismo nombre
+        assert_eq!(
+            client.try_reclamar_nombre(&eva, &String::from_str(&env, "aNa")),
+            Err(Ok(ErrorExtra::NombreOcupado.into()))
+        );
+        assert_eq!(
+            client.try_reclamar_nombre(&ana, &String::from_str(&env, "Otra")),
+            Err(Ok(ErrorExtra::YaTieneNombre.into()))
+        );
+
+        // Transferir: el nombre pasa a Eva y Ana queda libre
+        client.transferir_nombre(&ana, &eva);
+        assert_eq!(client.quien_es(&String::from_str(&env, "ana")), Some(eva.clone()));
+        assert_eq!(client.get_nombre_registrado(&ana), None);
+        assert_eq!(client.try_liberar_nombre(&ana), Err(Ok(ErrorExtra::SinNombreRegistrado.into())));
+
+        // Liberar: cualquiera lo puede volver a reclamar
+        client.liberar_nombre(&eva);
+        assert_eq!(client.quien_es(&String::from_str(&env, "ana")), None);
+        client.reclamar_nombre(&ana, &String::from_str(&env, "ana"));
+    }
+
+    #[test]
+    fn test_hello_requiere_nombre_registrado() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        client.initialize(&admin);
+
+        client.reclamar_nombre(&ana, &String::from_str(&env, "Ana"));
+
+        // Sin la opción activa cualquiera usa cualquier nombre
+        client.hello(&eva, &String::from_str(&env, "Ana"));
+
+        client.set_requiere_nombre_registrado(&admin, &true);
+        assert_eq!(
+            client.try_hello(&eva, &String::from_str(&env, "Ana")),
+            Err(Ok(Error::NombreNoCoincide))
+        );
+        assert_eq!(
+            client.try_hello(&eva, &String::from_str(&env, "Eva")),
+            Err(Ok(Error::NombreNoCoincide))
+        );
+        client.hello(&ana, &String::from_str(&env, "ANA"));
+        assert_eq!(client.get_contador(), 2);
+
+        // El límite de nombre también aplica al registro
+        client.set_limite_nombre(&admin, &3);
+        assert_eq!(
+            client.try_reclamar_nombre(&eva, &String::from_str(&env, "Evita")),
+            Err(Ok(Error::NombreMuyLargo.into()))
+        );
     }
-    
+
     // ========================================================================
-    // FUNCIÓN: get_contador
+    // TEST: Saludos efímeros
     // ========================================================================
-    // Propósito: Consultar el contador total de saludos
-    // Retorna: u32 - Número total de saludos (nunca falla)
+    #[test]
+    fn test_hello_efimero() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+        client.initialize(&admin);
+
+        assert_eq!(client.get_saludo_efimero(&ana), EstadoEfimero::NuncaSaludo);
+
+        env.ledger().set_sequence_number(100);
+        assert_eq!(client.hello_efimero(&ana, &nombre, &50), String::from_str(&env, "Hola"));
+
+        // No cuenta como saludo normal
+        assert_eq!(client.get_contador(), 0);
+        assert_eq!(client.get_contador_usuario(&ana), 0);
+        assert_eq!(client.get_ultimo_saludo(&ana), None);
+
+        env.ledger().set_sequence_number(150);
+        assert_eq!(client.get_saludo_efimero(&ana), EstadoEfimero::Vigente(nombre.clone()));
+
+        env.ledger().set_sequence_number(151);
+        assert_eq!(client.get_saludo_efimero(&ana), EstadoEfimero::Vencido);
+    }
+
+    #[test]
+    fn test_hello_efimero_duracion() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+        client.initialize(&admin);
+
+        assert_eq!(
+            client.try_hello_efimero(&ana, &nombre, &0),
+            Err(Ok(ErrorExtra::DuracionInvalida.into()))
+        );
+
+        client.set_max_duracion_efimero(&admin, &20);
+        assert_eq!(client.get_max_duracion_efimero(), 20);
+        assert_eq!(
+            client.try_hello_efimero(&ana, &nombre, &21),
+            Err(Ok(ErrorExtra::DuracionInvalida.into()))
+        );
+        client.hello_efimero(&ana, &nombre, &20);
+
+        // El tope no puede pasar el TTL de los temporary
+        assert_eq!(
+            client.try_set_max_duracion_efimero(&admin, &(MAX_DURACION_EFIMERO + 1)),
+            Err(Ok(ErrorExtra::DuracionInvalida.into()))
+        );
+        env.ledger().with_mut(|ledger| ledger.max_entry_ttl = 1_001);
+        assert_eq!(
+            client.try_set_max_duracion_efimero(&admin, &1_001),
+            Err(Ok(ErrorExtra::DuracionInvalida.into()))
+        );
+        client.set_max_duracion_efimero(&admin, &1_000);
+        client.hello_efimero(&ana, &nombre, &1_000);
+
+        // Las validaciones de hello() también aplican
+        assert_eq!(
+            client.try_hello_efimero(&ana, &String::from_str(&env, ""), &5),
+            Err(Ok(Error::NombreVacio.into()))
+        );
+    }
+
     // ========================================================================
-    pub fn get_contador(env: Env) -> u32 {
-        // Esta es una función de SOLO LECTURA
-        // No modifica estado → más barata de ejecutar
-        // Retorna u32 directamente (no Result) porque nunca falla
-        
-        env.storage()
-            .instance()                          // Leer de instance storage
-            .get(&DataKey::ContadorSaludos)      // Obtener contador
-            .unwrap_or(0)                        // Si no existe, retornar 0
-        
-        // unwrap_or(0) es seguro aquí porque:
-        // - No existe = 0 es un valor válido (nadie ha saludado)
-        // - No hay posibilidad de panic
+    // TEST: Compromiso y revelación
+    // ========================================================================
+    fn hash_de(env: &Env, nombre: &str, salt: &[u8]) -> BytesN<32> {
+        let mut datos = Bytes::from_slice(env, nombre.as_bytes());
+        datos.extend_from_slice(salt);
+        env.crypto().sha256(&datos).to_bytes()
+    }
+
+    #[test]
+    fn test_comprometer_y_revelar() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Sorpresa");
+        let salt = Bytes::from_slice(&env, b"sal secreta");
+        client.initialize(&admin);
+        client.set_ventana_revelacion(&admin, &VentanaRevelacion { espera: 5, duracion: 20 });
+
+        env.ledger().set_sequence_number(100);
+        let compromiso = client.comprometer(&ana, &hash_de(&env, "Sorpresa", b"sal secreta"));
+        assert_eq!((compromiso.desde, compromiso.hasta), (105, 125));
+
+        // Antes de la ventana no se puede revelar
+        env.ledger().set_sequence_number(104);
+        assert_eq!(
+            client.try_revelar(&ana, &nombre, &salt),
+            Err(Ok(ErrorExtra::RevelacionTemprana.into()))
+        );
+
+        // Un salt distinto no coincide con el hash
+        env.ledger().set_sequence_number(105);
+        assert_eq!(
+            client.try_revelar(&ana, &nombre, &Bytes::from_slice(&env, b"otra sal")),
+            Err(Ok(ErrorExtra::RevelacionInvalida.into()))
+        );
+
+        assert_eq!(client.revelar(&ana, &nombre, &salt), String::from_str(&env, "Hola"));
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(nombre.clone()));
+        assert_eq!(client.get_contador(), 1);
+
+        // Se usa una sola vez
+        assert_eq!(
+            client.try_revelar(&ana, &nombre, &salt),
+            Err(Ok(ErrorExtra::CompromisoNoExiste.into()))
+        );
+    }
+
+    #[test]
+    fn test_compromiso_vence() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_ventana_revelacion(&admin, &VentanaRevelacion { espera: 0, duracion: 20 });
+
+        env.ledger().set_sequence_number(100);
+        client.comprometer(&ana, &hash_de(&env, "Ana", b"sal"));
+        assert!(client.get_compromiso(&ana).is_some());
+
+        env.ledger().set_sequence_number(121);
+        assert_eq!(client.get_compromiso(&ana), None);
+        assert_eq!(
+            client.try_revelar(&ana, &String::from_str(&env, "Ana"), &Bytes::from_slice(&env, b"sal")),
+            Err(Ok(ErrorExtra::CompromisoNoExiste.into()))
+        );
+        assert_eq!(client.get_contador(), 0);
     }
-    
+
     // ========================================================================
-    // FUNCIÓN: get_ultimo_saludo
+    // TEST: Saludos en lote
     // ========================================================================
-    // Propósito: Consultar el último saludo de un usuario específico
-    // Parámetros:
-    //   - env: Ambiente de Soroban
-    //   - usuario: Dirección del usuario a consultar
-    // Retorna: Option<String> - Some(nombre) si existe, None si nunca saludó
+    #[test]
+    fn test_hello_lote_codigos_por_entrada() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        let bloqueada = Address::generate(&env);
+
+        client.initialize(&admin);
+        client.bloquear(&admin, &vec![&env, bloqueada.clone()]);
+
+        let entradas = vec![
+            &env,
+            (ana.clone(), String::from_str(&env, "Ana")),
+            (eva.clone(), String::from_str(&env, "")),
+            (bloqueada.clone(), String::from_str(&env, "Bea")),
+            (eva.clone(), String::from_str(&env, "Eva")),
+            (ana.clone(), String::from_str(&env, "Ana II")),
+        ];
+        let codigos = client.hello_lote(&None, &entradas);
+
+        assert_eq!(
+            codigos,
+            vec![
+                &env,
+                LOTE_OK,
+                Error::NombreVacio as u32,
+                Error::UsuarioBloqueado as u32,
+                LOTE_OK,
+                LOTE_OK
+            ]
+        );
+        assert_eq!(client.get_contador(), 3);
+        assert_eq!(client.get_contador_usuario(&ana), 2);
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(String::from_str(&env, "Ana II")));
+        assert_eq!(client.get_ultimo_saludo(&bloqueada), None);
+    }
+
+    #[test]
+    fn test_hello_lote_operador() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let operador = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        let entradas = vec![
+            &env,
+            (ana.clone(), String::from_str(&env, "Ana")),
+            (eva.clone(), String::from_str(&env, "Eva")),
+        ];
+
+        client.initialize(&admin);
+        assert_eq!(
+            client.try_hello_lote(&Some(operador.clone()), &entradas),
+            Err(Ok(Error::NoAutorizado))
+        );
+
+        client.agregar_operador(&admin, &operador);
+        client.hello_lote(&Some(operador.clone()), &entradas);
+
+        // Solo firmó el operador, no los asistentes
+        let firmas = env.auths();
+        assert_eq!(firmas.len(), 1);
+        assert_eq!(firmas[0].0, operador);
+        assert_eq!(client.get_contador(), 2);
+    }
+
+    #[test]
+    fn test_hello_lote_muy_grande() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let mut entradas = Vec::new(&env);
+        for _ in 0..=MAX_LOTE {
+            entradas.push_back((ana.clone(), String::from_str(&env, "Ana")));
+        }
+
+        assert_eq!(client.try_hello_lote(&None, &entradas), Err(Ok(Error::LoteMuyGrande)));
+    }
+
     // ========================================================================
-    pub fn get_ultimo_saludo(env: Env, usuario: Address) -> Option<String> {
-        // Función de SOLO LECTURA
-        // Retorna Option<String> porque el usuario puede no haber saludado nunca
-        
-        env.storage()
-            .persistent()                         // Leer de persistent storage
-            .get(&DataKey::UltimoSaludo(usuario)) // Key compuesta por usuario
-        
-        // .get() retorna Option<String> automáticamente
-        // - Some(nombre) = el usuario ha saludado
-        // - None = el usuario nunca ha saludado (válido)
-        
-        // NO usamos unwrap_or() porque queremos distinguir:
-        //   - "No ha saludado" (None) vs "Saludó con texto vacío" (Some(""))
+    // TEST: Consultas múltiples
+    // ========================================================================
+    #[test]
+    fn test_consultas_multiples() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let eva = Address::generate(&env);
+        let nadie = Address::generate(&env);
+
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+        client.hello(&eva, &String::from_str(&env, "Eva"));
+
+        // Mismo orden que la entrada, con repetidos incluidos
+        let usuarios = vec![&env, eva.clone(), nadie.clone(), ana.clone(), eva.clone()];
+        assert_eq!(
+            client.get_saludos_multiples(&usuarios),
+            vec![
+                &env,
+                Some(String::from_str(&env, "Eva")),
+                None,
+                Some(String::from_str(&env, "Ana")),
+                Some(String::from_str(&env, "Eva"))
+            ]
+        );
+        assert_eq!(client.get_contadores_multiples(&usuarios), vec![&env, 1, 0, 2, 1]);
+        assert_eq!(client.get_contadores_multiples(&Vec::new(&env)), Vec::new(&env));
+    }
+
+    #[test]
+    fn test_consultas_multiples_tope() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let mut usuarios = Vec::new(&env);
+        for _ in 0..MAX_CONSULTA {
+            usuarios.push_back(Address::generate(&env));
+        }
+        assert_eq!(client.get_contadores_multiples(&usuarios).len(), MAX_CONSULTA);
+
+        usuarios.push_back(Address::generate(&env));
+        assert_eq!(
+            client.try_get_saludos_multiples(&usuarios),
+            Err(Ok(ErrorExtra::ConsultaMuyGrande.into()))
+        );
+        assert_eq!(
+            client.try_get_contadores_multiples(&usuarios),
+            Err(Ok(ErrorExtra::ConsultaMuyGrande.into()))
+        );
     }
-    
-    // ========================================================================
-    // FUNCIÓN: reset_contador
+
     // ========================================================================
-    // Propósito: Resetear el contador a 0 (SOLO ADMIN)
-    // Parámetros:
-    //   - env: Ambiente de Soroban
-    //   - caller: Dirección de quien llama esta función
-    // Retorna: Result<(), Error> - Ok si exitoso, Error si no autorizado
+    // TEST: Información del contrato
     // ========================================================================
-    pub fn reset_contador(env: Env, caller: Address) -> Result<(), Error> {
-        
-        // PASO 1: Obtener el admin guardado en storage
-        // ---------------------------------------------
-        // Patrón de verificación de permisos:
-        
-        let admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)              // Intentar leer admin
-            .ok_or(Error::NoInicializado)?;    // Si None → Error #4
-        
-        // Desglose de .ok_or()?:
-        //   - .ok_or() convierte Option → Result
-        //     • Some(admin) → Ok(admin)
-        //     • None → Err(NoInicializado)
-        //   - El ? propaga el error automáticamente
-        //     • Si es Err, la función retorna inmediatamente
-        //     • Si es Ok, extrae el valor (admin)
-        
-        // PASO 2: Verificar que caller sea el admin
-        // ------------------------------------------
-        // Control de acceso: solo el admin puede resetear
-        if caller != admin {
-            // Early return si no está autorizado
-            // Error #3 = NoAutorizado
-            return Err(Error::NoAutorizado);
+    #[test]
+    fn test_info_version_y_modos() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let info = client.get_info();
+        assert_eq!(info.version, Version { mayor: 2, menor: 0, parche: 0 });
+        assert_eq!(info.version_esquema, VERSION_ESQUEMA);
+        for nombre in ["passkeys", "lote", "consultas_multiples", "saturar_contadores"] {
+            assert!(info.funcionalidades.contains(Symbol::new(&env, nombre)));
         }
-        
-        // PASO 3: Resetear el contador a 0
-        // ---------------------------------
-        // Si llegamos aquí, caller ES el admin
-        // Seguro modificar el estado
-        env.storage()
-            .instance()
-            .set(&DataKey::ContadorSaludos, &0u32);
-        
-        // PASO 4: Retornar éxito
-        // -----------------------
-        Ok(())
+        assert_eq!(info.activas.len(), 0);
+
+        // Encender modos opcionales se refleja en `activas`
+        client.set_modo_lista(&admin, &true);
+        client.set_raiz_merkle(&admin, &BytesN::from_array(&env, &[1; 32]));
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar);
+        assert_eq!(
+            client.get_info().activas,
+            vec![
+                &env,
+                Symbol::new(&env, "lista_permitidos"),
+                Symbol::new(&env, "merkle"),
+                Symbol::new(&env, "saturar_contadores"),
+            ]
+        );
+
+        // Fallar es el modo por defecto: no cuenta como activo
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Fallar);
+        assert_eq!(client.get_info().activas.len(), 2);
     }
-}
 
-// ============================================================================
-// MÓDULO DE TESTS
-// ============================================================================
-// #[cfg(test)] = este código solo se compila en modo test
-// Permite verificar que el contrato funciona correctamente antes de deploy
-// ============================================================================
+    #[test]
+    fn test_info_tabla_errores() {
+        let env = Env::default();
+        let contract_id = env.register(HelloContract, (Address::generate(&env),));
+        let client = HelloContractClient::new(&env, &contract_id);
 
-#[cfg(test)]
-mod test {
-    // Importar todo del módulo padre (el contrato)
-    use super::*;
-    // Importar utilidades de testing de Soroban
-    use soroban_sdk::{Env, testutils::Address as TestAddress};
+        let errores = client.get_info().errores;
+
+        // Un código está en la tabla si y solo si alguna variante lo usa
+        let codigos: std::vec::Vec<u32> = errores.iter().map(|fila| fila.codigo).collect();
+        for codigo in 1..=100 {
+            let error = soroban_sdk::Error::from_contract_error(codigo);
+            let existe = Error::try_from(error).is_ok() || ErrorExtra::try_from(error).is_ok();
+            assert_eq!(existe, codigos.contains(&codigo), "código {}", codigo);
+        }
+        assert!(codigos.windows(2).all(|par| par[0] < par[1]));
+
+        assert_eq!(errores.get_unchecked(2).nombre, Symbol::new(&env, "NoAutorizado"));
+    }
 
     // ========================================================================
-    // TEST: Inicialización básica
+    // TEST: Códigos de error estables
     // ========================================================================
-    // Verifica que el contrato se pueda inicializar correctamente
+    // Una fila por variante con el número escrito a mano: si alguien
+    // renumera una variante, este test falla antes de romper a los clientes
     // ========================================================================
     #[test]
-    fn test_initialize() {
-        // ARRANGE: Preparar el ambiente de test
-        // --------------------------------------
-        // Env::default() crea un ambiente simulado de blockchain
+    fn test_codigos_de_error_estables() {
+        let codigos = [
+            (Error::NombreVacio as u32, 1),
+            (Error::NombreMuyLargo as u32, 2),
+            (Error::NoAutorizado as u32, 3),
+            (Error::NoInicializado as u32, 4),
+            (Error::LimiteInvalido as u32, 5),
+            (Error::SalaYaExiste as u32, 6),
+            (Error::SalaNoExiste as u32, 7),
+            (Error::HookFallido as u32, 8),
+            (Error::DemasiadosHooks as u32, 9),
+            (Error::HookNoExiste as u32, 10),
+            (Error::UsuarioBloqueado as u32, 11),
+            (Error::NoEstaEnLista as u32, 12),
+            (Error::LoteMuyGrande as u32, 13),
+            (Error::RaizNoConfigurada as u32, 14),
+            (Error::PruebaInvalida as u32, 15),
+            (Error::HojaYaUsada as u32, 16),
+            (Error::FirmaExpirada as u32, 17),
+            (Error::NonceInvalido as u32, 18),
+            (Error::PasskeyYaRegistrada as u32, 19),
+            (Error::PasskeyNoRegistrada as u32, 20),
+            (Error::ConsejoInvalido as u32, 21),
+            (Error::RequiereConsejo as u32, 22),
+            (Error::PropuestaNoExiste as u32, 23),
+            (Error::PropuestaExpirada as u32, 24),
+            (Error::PropuestaCerrada as u32, 25),
+            (Error::YaAprobada as u32, 26),
+            (Error::DemasiadasPropuestas as u32, 27),
+            (Error::RequiereProgramacion as u32, 28),
+            (Error::CambioNoExiste as u32, 29),
+            (Error::CambioNoListo as u32, 30),
+            (Error::DemasiadosCambios as u32, 31),
+            (Error::RetrasoInvalido as u32, 32),
+            (Error::VotacionNoExiste as u32, 33),
+            (Error::VotacionCerrada as u32, 34),
+            (Error::YaVoto as u32, 35),
+            (Error::SinPesoDeVoto as u32, 36),
+            (Error::VotacionAbierta as u32, 37),
+            (Error::GobernanzaInvalida as u32, 38),
+            (Error::UsuarioSuspendido as u32, 39),
+            (Error::AccionNoExiste as u32, 40),
+            (Error::YaApelada as u32, 41),
+            (Error::ApelacionNoPendiente as u32, 42),
+            (Error::SinSaludo as u32, 43),
+            (Error::ApelacionInvalida as u32, 44),
+            (Error::NombreProhibido as u32, 45),
+            (Error::PalabraInvalida as u32, 46),
+            (Error::DemasiadasPalabras as u32, 47),
+            (Error::NombreNoCoincide as u32, 48),
+            (Error::YaInicializado as u32, 49),
+            (Error::ContadorDesbordado as u32, 50),
+            (ErrorExtra::IdiomaNoSoportado as u32, 51),
+            (ErrorExtra::SaludoInvalido as u32, 52),
+            (ErrorExtra::DemasiadosIdiomas as u32, 53),
+            (ErrorExtra::IdiomaPorDefecto as u32, 54),
+            (ErrorExtra::PlantillaInvalida as u32, 55),
+            (ErrorExtra::PlantillaNoExiste as u32, 56),
+            (ErrorExtra::BioMuyLarga as u32, 57),
+            (ErrorExtra::SinPerfil as u32, 58),
+            (ErrorExtra::NombreOcupado as u32, 59),
+            (ErrorExtra::YaTieneNombre as u32, 60),
+            (ErrorExtra::SinNombreRegistrado as u32, 61),
+            (ErrorExtra::DuracionInvalida as u32, 62),
+            (ErrorExtra::CompromisoNoExiste as u32, 63),
+            (ErrorExtra::RevelacionTemprana as u32, 64),
+            (ErrorExtra::RevelacionInvalida as u32, 65),
+            (ErrorExtra::ConsultaMuyGrande as u32, 66),
+            (ErrorExtra::MotivoInvalido as u32, 67),
+            (ErrorExtra::RetencionInvalida as u32, 68),
+            (ErrorExtra::DatosWebAuthnInvalidos as u32, 69),
+        ];
+
+        for (codigo, esperado) in codigos {
+            assert_eq!(codigo, esperado);
+        }
+        assert_eq!(codigos.len(), TABLA_ERRORES.len());
+    }
+
+    #[test]
+    fn test_ya_inicializado_y_no_inicializado() {
         let env = Env::default();
-        
-        // Registrar el contrato en el ambiente de test
-        // .register() retorna el ID del contrato
-        let contract_id = env.register(HelloContract, ());
-        
-        // Crear un cliente para interactuar con el contrato
-        // El SDK genera HelloContractClient automáticamente
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
         let client = HelloContractClient::new(&env, &contract_id);
-        
-        // Generar una dirección aleatoria para admin
+
         let admin = Address::generate(&env);
-        
-        // ACT: Ejecutar la acción a testear
-        // ----------------------------------
+
+        // Antes de initialize: NoInicializado
+        assert_eq!(client.try_reset_contador(&admin), Err(Ok(Error::NoInicializado)));
+
+        // Después, un segundo initialize: YaInicializado (ya no es #4)
         client.initialize(&admin);
-        
-        // ASSERT: Verificar el resultado esperado
-        // ----------------------------------------
-        // El contador debe estar en 0 después de inicializar
-        assert_eq!(client.get_contador(), 0);
+        assert_eq!(client.try_initialize(&admin), Err(Ok(Error::YaInicializado)));
+        assert_eq!(client.get_admin(), admin);
     }
-    
-    // ========================================================================
-    // TEST: No permitir re-inicialización
+
     // ========================================================================
-    // Verifica que el contrato no se pueda inicializar dos veces
-    // #[should_panic] = esperamos que este test cause panic
+    // TEST: Guardia de inicialización y los dos estilos de despliegue
     // ========================================================================
     #[test]
-    #[should_panic(expected = "Error(Contract, #4)")]  // Error #4 = NoInicializado
-    fn test_no_reinicializar() {
-        // ARRANGE
+    fn test_despliegue_con_constructor() {
         let env = Env::default();
-        let contract_id = env.register(HelloContract, ());
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Queda inicializado igual que con initialize()
+        assert_eq!(client.get_admin(), admin.clone());
+        assert_eq!(client.get_limite_nombre(), LIMITE_POR_DEFECTO);
+        assert_eq!(client.try_initialize(&admin), Err(Ok(Error::YaInicializado)));
+
+        client.hello(&Address::generate(&env), &String::from_str(&env, "Ana"));
+        assert_eq!(client.get_contador(), 1);
+    }
+
+    #[test]
+    fn test_despliegue_en_dos_pasos() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HelloContract, SIN_ADMIN);
         let client = HelloContractClient::new(&env, &contract_id);
-        
+
         let admin = Address::generate(&env);
-        
-        // ACT
-        client.initialize(&admin);      // Primera inicialización - OK
-        client.initialize(&admin);      // Segunda inicialización - DEBE FALLAR
-        
-        // ASSERT: El test PASA si hay panic con el mensaje esperado
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+
+        // Antes de initialize nada escribe estado
+        assert_eq!(client.try_hello(&ana, &nombre), Err(Ok(Error::NoInicializado)));
+        assert_eq!(
+            client.try_hello_lote(&None, &vec![&env, (ana.clone(), nombre.clone())]),
+            Err(Ok(Error::NoInicializado))
+        );
+        assert_eq!(
+            client.try_set_perfil(&ana, &nombre, &nombre, &None, &None),
+            Err(Ok(Error::NoInicializado.into()))
+        );
+        assert_eq!(
+            client.try_comprometer(&ana, &BytesN::from_array(&env, &[0; 32])),
+            Err(Ok(Error::NoInicializado))
+        );
+
+        assert_eq!(
+            client.try_crear_sala(&admin, &symbol_short!("meetup"), &ana, &32),
+            Err(Ok(Error::NoInicializado))
+        );
+        assert_eq!(client.try_liberar_nombre(&ana), Err(Ok(Error::NoInicializado.into())));
+        assert_eq!(
+            client.try_transferir_nombre(&ana, &admin),
+            Err(Ok(Error::NoInicializado.into()))
+        );
+        assert_eq!(
+            client.try_apelar(&ana, &0, &nombre),
+            Err(Ok(Error::NoInicializado))
+        );
+        assert_eq!(
+            client.try_quitar_passkey(&ana, &BytesN::from_array(&env, &[4; 65])),
+            Err(Ok(Error::NoInicializado))
+        );
+        assert_eq!(client.try_ejecutar_cambio(&0), Err(Ok(Error::NoInicializado)));
+
+        // Ni se lee: un 0 o un None antes de initialize parecería un
+        // contrato en uso sin datos
+        assert_eq!(client.try_get_contador(), Err(Ok(Error::NoInicializado)));
+        assert_eq!(client.try_get_ultimo_saludo(&ana), Err(Ok(Error::NoInicializado)));
+        assert_eq!(client.try_get_admin(), Err(Ok(Error::NoInicializado)));
+        assert_eq!(
+            client.try_get_contador_sala(&symbol_short!("meetup")),
+            Err(Ok(Error::NoInicializado))
+        );
+        assert_eq!(
+            client.try_get_contadores_multiples(&vec![&env, ana.clone()]),
+            Err(Ok(Error::NoInicializado.into()))
+        );
+        assert_eq!(client.try_get_info(), Err(Ok(Error::NoInicializado)));
+        let creado = env.as_contract(&contract_id, || {
+            env.storage().instance().has(&DataKey::ContadorSaludos)
+        });
+        assert!(!creado);
+
+        // Los mensajes a firmar no leen storage: se pueden preparar antes
+        client.get_challenge_passkey(&nombre, &0);
+
+        client.initialize(&admin);
+        assert_eq!(client.get_contador(), 0);
+        client.hello(&ana, &nombre);
+        assert_eq!(client.get_contador(), 1);
     }
-    
-    // ========================================================================
-    // TEST: Función hello exitosa
+
+    #[test]
+    fn test_codigo_v1_compatible() {
+        // Solo YaInicializado cambia: un cliente v1 lo sigue viendo como #4
+        assert_eq!(Error::YaInicializado.codigo_v1(), 4);
+        assert_eq!(Error::NoInicializado.codigo_v1(), 4);
+        for (codigo, _) in TABLA_ERRORES {
+            if let Ok(error) = Error::try_from(soroban_sdk::Error::from_contract_error(codigo)) {
+                if error != Error::YaInicializado {
+                    assert_eq!(error.codigo_v1(), codigo);
+                }
+            }
+        }
+    }
+
     // ========================================================================
-    // Verifica que hello() funcione correctamente con inputs válidos
+    // TEST: Contadores u64 y desbordamiento
     // ========================================================================
     #[test]
-    fn test_hello_exitoso() {
-        // ARRANGE
+    fn test_contador_desborda_o_satura() {
         let env = Env::default();
-        let contract_id = env.register(HelloContract, ());
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
         let client = HelloContractClient::new(&env, &contract_id);
-        
-        // Generar dos direcciones: admin y usuario normal
+
+        let ana = Address::generate(&env);
+        let nombre = String::from_str(&env, "Ana");
+
+        // Ya pasó u32::MAX sin problema
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::ContadorSaludos, &(u64::MAX - 1));
+        });
+        client.hello(&ana, &nombre);
+        assert_eq!(client.get_contador(), u64::MAX);
+
+        // Por defecto falla y no escribe nada
+        assert_eq!(client.get_modo_desbordamiento(), ModoDesbordamiento::Fallar);
+        assert_eq!(client.try_hello(&ana, &nombre), Err(Ok(Error::ContadorDesbordado)));
+        assert_eq!(client.get_contador_usuario(&ana), 1);
+
+        // En el lote es un error de esa entrada
+        assert_eq!(
+            client.hello_lote(&None, &vec![&env, (ana.clone(), nombre.clone())]),
+            vec![&env, Error::ContadorDesbordado as u32]
+        );
+
+        // Saturar: el saludo pasa y el contador se queda en el máximo
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar);
+        client.hello(&ana, &nombre);
+        assert_eq!(client.get_contador(), u64::MAX);
+        assert_eq!(client.get_contador_usuario(&ana), 2);
+    }
+
+    #[test]
+    fn test_contador_usuario_desborda() {
+        let env = Env::default();
+        env.mock_all_auths();
         let admin = Address::generate(&env);
-        let usuario = Address::generate(&env);
-        
-        // Inicializar el contrato primero
-        client.initialize(&admin);
-        
-        // ACT
-        // Crear un String en el ambiente de Soroban
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
         let nombre = String::from_str(&env, "Ana");
-        let resultado = client.hello(&usuario, &nombre);
-        
-        // ASSERT: Verificar múltiples condiciones
-        // ----------------------------------------
-        // 1. La función retorna "Hola"
-        assert_eq!(resultado, Symbol::new(&env, "Hola"));
-        
-        // 2. El contador se incrementó a 1
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::ContadorPorUsuario(ana.clone()), &u64::MAX);
+        });
+
+        // El contador de la sala no avanza si el del usuario desborda
+        assert_eq!(client.try_hello(&ana, &nombre), Err(Ok(Error::ContadorDesbordado)));
+        assert_eq!(client.get_contador(), 0);
+
+        // Saturado, el peso de voto es el contador completo
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar);
+        client.hello(&ana, &nombre);
         assert_eq!(client.get_contador(), 1);
-        
-        // 3. El saludo del usuario se guardó correctamente
-        assert_eq!(client.get_ultimo_saludo(&usuario), Some(nombre));
+        assert_eq!(client.get_contador_usuario(&ana), u64::MAX);
+        let peso = env.as_contract(&contract_id, || peso_de_voto(&env, &ana, u32::MAX));
+        assert_eq!(peso, u64::MAX);
     }
-    
-    // ========================================================================
-    // TEST: Validación de nombre vacío
+
+    #[test]
+    fn test_migrar_contadores_u32() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let beto = Address::generate(&env);
+
+        // Estado como lo dejaba el esquema 1
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::ContadorSaludos, &7u32);
+            env.storage().instance().remove(&DataKeyExtra::VersionEsquema);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ContadorPorUsuario(ana.clone()), &3u32);
+        });
+        assert_eq!(client.get_info().version_esquema, 1);
+        assert_eq!(client.get_contador(), 7);
+        assert_eq!(client.get_contador_usuario(&ana), 3);
+
+        assert_eq!(client.migrar_esquema(&admin), 2);
+        client.migrar_contadores_usuarios(&admin, &vec![&env, ana.clone(), beto.clone()]);
+        assert_eq!(client.get_info().version_esquema, 2);
+
+        let (global, usuario, sin_saludos) = env.as_contract(&contract_id, || {
+            let global: Option<u64> = env.storage().instance().get(&DataKey::ContadorSaludos);
+            let usuario: Option<u64> = env.storage()
+                .persistent()
+                .get(&DataKey::ContadorPorUsuario(ana.clone()));
+            let sin_saludos = env.storage()
+                .persistent()
+                .has(&DataKey::ContadorPorUsuario(beto.clone()));
+            (global, usuario, sin_saludos)
+        });
+        assert_eq!((global, usuario, sin_saludos), (Some(7), Some(3), false));
+
+        // Solo el admin migra
+        assert_eq!(client.try_migrar_esquema(&ana), Err(Ok(Error::NoAutorizado)));
+    }
+
     // ========================================================================
-    // Verifica que hello() rechace nombres vacíos
+    // TEST: Ajustes del admin con auditoría
     // ========================================================================
     #[test]
-    #[should_panic(expected = "Error(Contract, #1)")]  // Error #1 = NombreVacio
-    fn test_nombre_vacio() {
-        // ARRANGE
+    fn test_ajustes_admin_auditados() {
         let env = Env::default();
-        let contract_id = env.register(HelloContract, ());
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
         let client = HelloContractClient::new(&env, &contract_id);
-        
+
+        let spammer = Address::generate(&env);
+        let nombre = String::from_str(&env, "Spam");
+        let motivo = String::from_str(&env, "saludos automatizados");
+        for _ in 0..5 {
+            client.hello(&spammer, &nombre);
+        }
+
+        // Contador global: queda el valor exacto y un evento con el cambio
+        env.ledger().set_sequence_number(42);
+        let id = client.set_contador(&admin, &2, &motivo);
+        let evento = Auditado {
+            accion: AccionAuditoria::SetContador,
+            id,
+            actor: admin.clone(),
+            usuario: None,
+            sala: None,
+            anterior: ValorAuditoria::Numero(5),
+            nuevo: ValorAuditoria::Numero(2),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), evento.topics(&env), evento.data(&env))]
+        );
+        assert_eq!(client.get_contador(), 2);
+        assert_eq!(
+            client.get_auditoria(&id),
+            Some(EntradaAuditoria {
+                id,
+                actor: admin.clone(),
+                accion: AccionAuditoria::SetContador,
+                usuario: None,
+                sala: None,
+                anterior: ValorAuditoria::Numero(5),
+                nuevo: ValorAuditoria::Numero(2),
+                motivo: Some(motivo.clone()),
+                ledger: 42,
+            })
+        );
+
+        // Contador del usuario
+        let id = client.ajustar_contador_usuario(&admin, &spammer, &0, &motivo);
+        assert_eq!(client.get_contador_usuario(&spammer), 0);
+        let entrada = client.get_auditoria(&id).unwrap();
+        assert_eq!(entrada.usuario, Some(spammer.clone()));
+        assert_eq!(entrada.anterior, ValorAuditoria::Numero(5));
+
+        // Último saludo
+        let id = client.borrar_ultimo_saludo(&admin, &spammer, &motivo);
+        assert_eq!(client.get_ultimo_saludo(&spammer), None);
+        let entrada = client.get_auditoria(&id).unwrap();
+        assert_eq!(entrada.anterior, ValorAuditoria::Texto(nombre));
+        assert_eq!(entrada.nuevo, ValorAuditoria::Ninguno);
+        assert_eq!(
+            client.try_borrar_ultimo_saludo(&admin, &spammer, &motivo),
+            Err(Ok(Error::SinSaludo.into()))
+        );
+        assert_eq!(client.get_auditoria(&(id + 1)), None);
+    }
+
+    #[test]
+    fn test_ajustes_admin_validaciones() {
+        let env = Env::default();
+        env.mock_all_auths();
         let admin = Address::generate(&env);
-        let usuario = Address::generate(&env);
-        
-        client.initialize(&admin);
-        
-        // ACT
-        // Crear un String vacío
-        let vacio = String::from_str(&env, "");
-        client.hello(&usuario, &vacio);  // DEBE FALLAR con Error #1
-        
-        // ASSERT: El test PASA si hay panic con Error #1
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let ana = Address::generate(&env);
+        let motivo = String::from_str(&env, "corrección");
+
+        // Sin motivo no hay ajuste
+        assert_eq!(
+            client.try_set_contador(&admin, &10, &String::from_str(&env, "")),
+            Err(Ok(ErrorExtra::MotivoInvalido.into()))
+        );
+        assert_eq!(
+            client.try_set_contador(&admin, &10, &String::from_bytes(&env, &[b'a'; 257])),
+            Err(Ok(ErrorExtra::MotivoInvalido.into()))
+        );
+
+        // Solo el admin
+        assert_eq!(
+            client.try_ajustar_contador_usuario(&ana, &ana, &100, &motivo),
+            Err(Ok(Error::NoAutorizado.into()))
+        );
+        assert_eq!(client.get_contador_usuario(&ana), 0);
+        assert_eq!(client.get_auditoria(&1), None);
+
+        // Con consejo, ninguno de los ajustes queda en manos del admin solo
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, Address::generate(&env)], umbral: 1 },
+        );
+        let requiere_consejo = Err(Ok(Error::RequiereConsejo.into()));
+        assert_eq!(client.try_set_contador(&admin, &10, &motivo), requiere_consejo);
+        assert_eq!(client.try_ajustar_contador_usuario(&admin, &ana, &10, &motivo), requiere_consejo);
+        assert_eq!(client.try_borrar_ultimo_saludo(&admin, &ana, &motivo), requiere_consejo);
+        assert_eq!(client.get_ultimo_saludo(&ana), Some(String::from_str(&env, "Ana")));
     }
-    
-    // ========================================================================
-    // TEST: Solo admin puede resetear
+
     // ========================================================================
-    // Verifica que el admin pueda resetear el contador
+    // TEST: Log de auditoría de acciones privilegiadas
     // ========================================================================
     #[test]
-    fn test_reset_solo_admin() {
-        // ARRANGE
+    fn test_auditoria_acciones_admin() {
         let env = Env::default();
-        let contract_id = env.register(HelloContract, ());
-        let client = HelloContractClient::new(&env, &contract_id);
-        
+        env.mock_all_auths();
         let admin = Address::generate(&env);
-        let usuario = Address::generate(&env);
-        
-        client.initialize(&admin);
-        
-        // ACT
-        // 1. Hacer un saludo (contador = 1)
-        client.hello(&usuario, &String::from_str(&env, "Test"));
-        assert_eq!(client.get_contador(), 1);
-        
-        // 2. Admin resetea el contador
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        let nuevo_admin = Address::generate(&env);
+        let ana = Address::generate(&env);
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+
+        env.ledger().set_sequence_number(10);
         client.reset_contador(&admin);
-        
-        // ASSERT
-        // El contador debe estar en 0 después del reset
-        assert_eq!(client.get_contador(), 0);
+        client.set_limite_nombre(&admin, &16);
+        client.transfer_admin(&admin, &nuevo_admin);
+
+        let entradas = client.get_auditorias(&0, &10);
+        assert_eq!(entradas.len(), 3);
+        assert_eq!(
+            entradas.get(0).unwrap(),
+            EntradaAuditoria {
+                id: 1,
+                actor: admin.clone(),
+                accion: AccionAuditoria::ResetContador,
+                usuario: None,
+                sala: None,
+                anterior: ValorAuditoria::Numero(1),
+                nuevo: ValorAuditoria::Numero(0),
+                motivo: None,
+                ledger: 10,
+            }
+        );
+        let limite = entradas.get(1).unwrap();
+        assert_eq!(limite.accion, AccionAuditoria::SetLimiteNombre);
+        assert_eq!((limite.anterior, limite.nuevo), (ValorAuditoria::Numero(32), ValorAuditoria::Numero(16)));
+        let transferencia = entradas.get(2).unwrap();
+        assert_eq!(transferencia.accion, AccionAuditoria::TransferAdmin);
+        assert_eq!(transferencia.anterior, ValorAuditoria::Direccion(admin.clone()));
+        assert_eq!(transferencia.nuevo, ValorAuditoria::Direccion(nuevo_admin.clone()));
+
+        // Una acción rechazada no deja entrada
+        assert_eq!(client.try_reset_contador(&admin), Err(Ok(Error::NoAutorizado)));
+        assert_eq!(client.get_rango_auditoria(), (1, 4));
+
+        // Paginación
+        let pagina = client.get_auditorias(&2, &1);
+        assert_eq!(pagina.len(), 1);
+        assert_eq!(pagina.get(0).unwrap().id, 2);
+        assert_eq!(client.get_auditorias(&4, &10).len(), 0);
+        assert_eq!(
+            client.try_get_auditorias(&0, &(MAX_CONSULTA + 1)),
+            Err(Ok(ErrorExtra::ConsultaMuyGrande.into()))
+        );
     }
-    
-    // ========================================================================
-    // TEST: No admin no puede resetear
-    // ========================================================================
-    // Verifica que usuarios NO admin no puedan resetear el contador
-    // ========================================================================
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")]  // Error #3 = NoAutorizado
-    fn test_reset_no_autorizado() {
-        // ARRANGE
+    fn test_auditoria_retencion_y_ttl() {
         let env = Env::default();
-        let contract_id = env.register(HelloContract, ());
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
         let client = HelloContractClient::new(&env, &contract_id);
-        
-        // Generar dos direcciones diferentes
+
+        assert_eq!(client.get_retencion_auditoria(), RETENCION_AUDITORIA_POR_DEFECTO);
+        assert_eq!(
+            client.try_set_retencion_auditoria(&admin, &(MAX_RETENCION_AUDITORIA + 1)),
+            Err(Ok(ErrorExtra::RetencionInvalida.into()))
+        );
+
+        // Entrada #1 (ledger 0) es el propio cambio de retención
+        let retencion = RETENCION_AUDITORIA_POR_DEFECTO + 100;
+        client.set_retencion_auditoria(&admin, &retencion);
+
+        // El TTL del log no es el de los datos de usuario, y cubre la retención
+        let ana = Address::generate(&env);
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+        let (ttl_auditoria, ttl_usuario) = env.as_contract(&contract_id, || {
+            (
+                env.storage().persistent().get_ttl(&DataKeyExtra::Auditoria(1)),
+                env.storage().persistent().get_ttl(&DataKey::UltimoSaludo(ana.clone())),
+            )
+        });
+        assert_eq!(ttl_auditoria, TTL_AUDITORIA);
+        assert!(ttl_auditoria >= MAX_RETENCION_AUDITORIA);
+        assert!(ttl_usuario < ttl_auditoria);
+
+        // La instancia vive lo mismo que el log para poder saltar de ledger
+        let mantener_instancia = || {
+            env.as_contract(&contract_id, || {
+                env.storage().instance().extend_ttl(TTL_AUDITORIA, TTL_AUDITORIA)
+            })
+        };
+        mantener_instancia();
+
+        env.ledger().set_sequence_number(retencion - 50);
+        client.reset_contador(&admin);  // #2
+
+        // En el ledger `retencion` la #1 ya venció: no se muestra aunque siga guardada
+        env.ledger().set_sequence_number(retencion);
+        assert_eq!(client.get_auditoria(&1), None);
+        let entradas = client.get_auditorias(&0, &10);
+        assert_eq!(entradas.len(), 1);
+        assert_eq!(entradas.get(0).unwrap().id, 2);
+
+        // La próxima entrada la poda; la #2 sigue dentro de la retención
+        client.reset_contador(&admin);  // #3
+        assert_eq!(client.get_rango_auditoria(), (2, 4));
+        let borrada = env.as_contract(&contract_id, || {
+            !env.storage().persistent().has(&DataKeyExtra::Auditoria(1))
+        });
+        assert!(borrada);
+
+        mantener_instancia();
+        env.ledger().set_sequence_number(2 * retencion - 51);
+        assert!(client.get_auditoria(&2).is_some());
+        env.ledger().set_sequence_number(2 * retencion - 50);
+        assert_eq!(client.get_auditoria(&2), None);
+        assert_eq!(client.get_auditorias(&0, &10).len(), 1);
+    }
+
+    #[test]
+    fn test_auditoria_retencion_no_baja() {
+        let env = Env::default();
+        env.mock_all_auths();
         let admin = Address::generate(&env);
-        let otro = Address::generate(&env);   // Usuario NO admin
-        
-        client.initialize(&admin);
-        
-        // ACT
-        // Un usuario NO admin intenta resetear
-        client.reset_contador(&otro);  // DEBE FALLAR con Error #3
-        
-        // ASSERT: El test PASA si hay panic con Error #3
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        client.reset_contador(&admin);  // #1
+        client.set_retencion_auditoria(&admin, &(RETENCION_AUDITORIA_POR_DEFECTO * 2));  // #2
+
+        // Bajar la retención (ni siquiera al valor por defecto) no se puede
+        env.ledger().set_sequence_number(10);
+        for retencion in [1, 10, RETENCION_AUDITORIA_POR_DEFECTO] {
+            assert_eq!(
+                client.try_set_retencion_auditoria(&admin, &retencion),
+                Err(Ok(ErrorExtra::RetencionInvalida.into()))
+            );
+        }
+
+        // Las entradas siguen visibles y nada se podó con la siguiente
+        client.reset_contador(&admin);  // #3
+        assert_eq!(client.get_rango_auditoria(), (1, 4));
+        assert_eq!(client.get_auditorias(&0, &10).len(), 3);
+
+        // Con consejo, ni subirla queda en manos del admin solo
+        client.configurar_consejo(
+            &admin,
+            &Consejo { firmantes: vec![&env, Address::generate(&env)], umbral: 1 },
+        );
+        assert_eq!(
+            client.try_set_retencion_auditoria(&admin, &MAX_RETENCION_AUDITORIA),
+            Err(Ok(Error::RequiereConsejo.into()))
+        );
+    }
+
+    #[test]
+    fn test_auditoria_consejo_votacion_y_configuracion() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let firmante = Address::generate(&env);
+        let moderador = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let contract_id = env.register(HelloContract, (admin.clone(),));
+        let client = HelloContractClient::new(&env, &contract_id);
+
+        // Configuración del admin
+        client.registrar_hook(&admin, &hook, &false);
+        client.quitar_hook(&admin, &hook);
+        client.agregar_moderador(&admin, &moderador);
+        client.quitar_moderador(&admin, &moderador);
+        let raiz = BytesN::from_array(&env, &[7; 32]);
+        client.set_raiz_merkle(&admin, &raiz);
+        client.set_modo_desbordamiento(&admin, &ModoDesbordamiento::Saturar);
+        client.configurar_gobernanza(&admin, &ConfigGobernanza { quorum: 1, periodo: 10 });
+        let consejo = Consejo { firmantes: vec![&env, firmante.clone()], umbral: 1 };
+        client.configurar_consejo(&admin, &consejo);
+
+        // Consejo (umbral 1: proponer ejecuta)
+        env.ledger().set_sequence_number(1);
+        client.hello(&ana, &String::from_str(&env, "Ana"));
+        client.proponer(&firmante, &AccionConsejo::ResetContador, &10);
+        client.proponer(&firmante, &AccionConsejo::SetLimiteNombre(20), &10);
+        let nuevo = Consejo { firmantes: vec![&env, firmante.clone(), admin.clone()], umbral: 1 };
+        client.proponer(&firmante, &AccionConsejo::CambiarConsejo(nuevo.clone()), &10);
+
+        // Votación
+        env.ledger().set_sequence_number(2);
+        let id = client.proponer_limite(&ana, &64);
+        client.votar(&ana, &id, &true);
+        env.ledger().set_sequence_number(13);
+        client.finalizar_votacion(&id);
+
+        let entradas = client.get_auditorias(&0, &20);
+        let mut acciones = Vec::new(&env);
+        for entrada in entradas.iter() {
+            acciones.push_back(entrada.accion);
+        }
+        assert_eq!(
+            acciones,
+            vec![
+                &env,
+                AccionAuditoria::RegistrarHook,
+                AccionAuditoria::QuitarHook,
+                AccionAuditoria::AgregarModerador,
+                AccionAuditoria::QuitarModerador,
+                AccionAuditoria::SetRaizMerkle,
+                AccionAuditoria::SetModoDesbordamiento,
+                AccionAuditoria::ConfigurarConsejo,
+                AccionAuditoria::ResetContadorConsejo,
+                AccionAuditoria::SetLimiteConsejo,
+                AccionAuditoria::CambiarConsejo,
+                AccionAuditoria::LimitePorVotacion,
+            ]
+        );
+
+        let merkle = entradas.get(4).unwrap();
+        assert_eq!((merkle.anterior, merkle.nuevo), (ValorAuditoria::Ninguno, ValorAuditoria::Hash(raiz)));
+
+        // Lo del consejo queda a nombre del firmante que lo ejecutó
+        let reset = entradas.get(7).unwrap();
+        assert_eq!(reset.actor, firmante);
+        assert_eq!((reset.anterior, reset.nuevo), (ValorAuditoria::Numero(1), ValorAuditoria::Numero(0)));
+        let cambio = entradas.get(9).unwrap();
+        assert_eq!(cambio.anterior, ValorAuditoria::Consejo(consejo));
+        assert_eq!(cambio.nuevo, ValorAuditoria::Consejo(nuevo));
+
+        // La votación no tiene firmante: queda a nombre del contrato
+        let votacion = entradas.get(10).unwrap();
+        assert_eq!(votacion.actor, contract_id);
+        assert_eq!((votacion.anterior, votacion.nuevo), (ValorAuditoria::Numero(20), ValorAuditoria::Numero(64)));
     }
 }
 
 // ============================================================================
 // RESUMEN DE PATRONES APLICADOS
 // ============================================================================
-// 
+//
 // 1. VALIDACIONES:
 //    - Orden: barato → caro (longitud antes de storage)
 //    - Early returns para fail fast
 //
 // 2. STORAGE:
-//    - Instance: datos globales (Admin, Contador)
-//    - Persistent: datos por usuario (UltimoSaludo)
+//    - Instance: datos globales (Admin, Contador, Límite)
+//    - Persistent: datos por usuario (UltimoSaludo) y por sala
 //    - Siempre extender TTL después de modificar
 //
 // 3. MANEJO DE ERRORES:
@@ -463,4 +8505,4 @@ mod test {
 //    - should_panic para verificar que errores se manejen bien
 //    - Testear casos exitosos Y casos de error
 //
-// ============================================================================
\ No newline at end of file
+// ============================================================================